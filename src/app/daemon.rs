@@ -0,0 +1,305 @@
+//! `--daemon`: keep the launcher resident and map its surface only while it is shown.
+//!
+//! The control socket accepts `show`, `hide`, `toggle` and `state`, or JSON requests such as
+//! `{"command": "panel", "panel": "music"}`. Every request is answered with one JSON line:
+//! `{"ok": true, "state": {...}}` or `{"ok": false, "error": "..."}`. Requests that look
+//! something up, like `clipboard_history`, add it as `"result"`.
+
+use std::sync::mpsc::{self, SyncSender};
+use std::time::Duration;
+
+use iced::futures::channel::mpsc::Sender;
+use iced::Task as Command;
+use iced::window;
+use iced_layershell::reexport::{Anchor, KeyboardInteractivity, Layer, NewLayerShellSettings};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::app::message::Message;
+use crate::app::state::{Launcher, Panel, SearchMode};
+use crate::app::update::update;
+use crate::panels::app_list::{self, AppList};
+use crate::panels::search_bar;
+use crate::utils::data;
+
+/// Surface geometry, shared with the one-shot launcher's settings
+pub const SIZE: (u32, u32) = (484, 714);
+pub const MARGIN: (i32, i32, i32, i32) = (0, 0, 4, 0);
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum Request {
+    Show,
+    Hide,
+    Toggle,
+    /// Only report the state
+    State,
+    /// Show the launcher on `panel`
+    Panel { panel: Panel },
+    /// Show the launcher with the search bar set to `mode`'s prefix
+    Mode { mode: SearchMode },
+    /// Show or hide the control center, toggling it without `visible`
+    ControlCenter { visible: Option<bool> },
+    /// Show or hide the clipboard history, toggling it without `visible`
+    Clipboard { visible: Option<bool> },
+    Wallpaper { direction: Step },
+    /// Switch to the wallpaper with this file name, with or without its extension
+    SetWallpaper { name: String },
+    /// Only report the clipboard history, newest first
+    ClipboardHistory,
+    ClipboardClear,
+    /// Percentages, clamped to 0-100
+    Volume { value: f32 },
+    Brightness { value: f32 },
+    Media { action: MediaAction },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Step {
+    Next,
+    Previous,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MediaAction {
+    PlayPause,
+    Next,
+    Previous,
+}
+
+impl Request {
+    /// A plain command word (as sent by `sierra-launcher toggle`) or a JSON object
+    pub fn parse(line: &str) -> Result<Self, String> {
+        if line.starts_with('{') {
+            return serde_json::from_str(line).map_err(|e| format!("invalid request: {}", e));
+        }
+        match line {
+            "show" => Ok(Self::Show),
+            "hide" => Ok(Self::Hide),
+            "toggle" => Ok(Self::Toggle),
+            "state" => Ok(Self::State),
+            _ => Err(format!("unknown command {:?}", line)),
+        }
+    }
+}
+
+/// How long a socket or D-Bus request waits for `update` to answer it
+const REPLY_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A handled request: the launcher's state, and what the request looked up if anything
+#[derive(Debug, Clone)]
+pub struct Answer {
+    pub state: Value,
+    pub result: Option<Value>,
+}
+
+/// Where the launcher sends its answer to a request, or what went wrong
+#[derive(Debug, Clone)]
+pub struct Reply(SyncSender<Result<Answer, String>>);
+
+impl Reply {
+    pub fn send(self, reply: Result<Answer, String>) {
+        // The requester may have timed out already
+        let _ = self.0.send(reply);
+    }
+}
+
+/// Hand `request` to `update` and wait for its answer. Blocks, so only call it off the UI thread.
+pub fn dispatch(output: &mut Sender<Message>, request: Request) -> Result<Answer, String> {
+    let (sender, answer) = mpsc::sync_channel(1);
+    output
+        .try_send(Message::Daemon(request, Reply(sender)))
+        .map_err(|_| "launcher is shutting down".to_string())?;
+    answer
+        .recv_timeout(REPLY_TIMEOUT)
+        .unwrap_or_else(|_| Err("launcher did not answer".to_string()))
+}
+
+/// The JSON line a socket client gets back
+pub fn reply_line(reply: Result<Answer, String>) -> String {
+    match reply {
+        Ok(Answer { state, result: None }) => json!({ "ok": true, "state": state }).to_string(),
+        Ok(Answer { state, result: Some(result) }) => {
+            json!({ "ok": true, "state": state, "result": result }).to_string()
+        }
+        Err(message) => json!({ "ok": false, "error": message }).to_string(),
+    }
+}
+
+pub fn handle(launcher: &mut Launcher, request: Request, reply: Reply) -> Command<Message> {
+    let result = match request {
+        Request::ClipboardHistory => Some(clipboard_history()),
+        _ => None,
+    };
+    match apply(launcher, request) {
+        Ok(command) => {
+            reply.send(Ok(Answer { state: state(launcher), result }));
+            command
+        }
+        Err(message) => {
+            reply.send(Err(message));
+            Command::none()
+        }
+    }
+}
+
+/// Carry out `request` through the same messages the UI sends
+fn apply(launcher: &mut Launcher, request: Request) -> Result<Command<Message>, String> {
+    let command = match request {
+        Request::Show => show(launcher),
+        Request::Hide => hide(launcher),
+        Request::Toggle if launcher.surface.is_some() => hide(launcher),
+        Request::Toggle => show(launcher),
+        Request::State => Command::none(),
+        Request::Panel { panel } => {
+            let shown = show(launcher);
+            Command::batch([shown, update(launcher, Message::SetPanel(panel))])
+        }
+        Request::Mode { mode } => {
+            let shown = show(launcher);
+            let query = mode.prefix().map(String::from).unwrap_or_default();
+            let input = search_bar::Message::InputChanged(query);
+            Command::batch([shown, update(launcher, Message::SearchBarMessage(input))])
+        }
+        Request::ControlCenter { visible } => {
+            // A hidden launcher shows up with it open
+            let visible = visible.unwrap_or(launcher.surface.is_none() || !launcher.control_center_visible);
+            if launcher.surface.is_none() && !visible {
+                return Ok(Command::none());
+            }
+            let shown = show(launcher);
+            if launcher.control_center_visible == visible {
+                return Ok(shown);
+            }
+            Command::batch([shown, update(launcher, Message::ToggleControlCenter)])
+        }
+        Request::Clipboard { visible } => {
+            // A hidden launcher shows up with it open
+            let visible = visible.unwrap_or(launcher.surface.is_none() || !launcher.clipboard_visible);
+            if launcher.surface.is_none() && !visible {
+                return Ok(Command::none());
+            }
+            let shown = show(launcher);
+            launcher.clipboard_visible = visible;
+            shown
+        }
+        Request::Wallpaper { direction } => {
+            if launcher.wallpaper_index.as_ref().is_none_or(|index| index.wallpapers.is_empty()) {
+                return Err("no wallpapers found".to_string());
+            }
+            match direction {
+                Step::Next => update(launcher, Message::NextWallpaper),
+                Step::Previous => update(launcher, Message::PrevWallpaper),
+            }
+        }
+        Request::SetWallpaper { name } => {
+            let Some(index) = launcher.wallpaper_index.as_ref().and_then(|index| index.position(&name)) else {
+                return Err(format!("no wallpaper named {:?}", name));
+            };
+            launcher.wallpaper_selected_index = index;
+            update(launcher, Message::SetWallpaper(index))
+        }
+        Request::ClipboardHistory => Command::none(),
+        Request::ClipboardClear => {
+            data::clear();
+            launcher.clipboard_selected_index = 0;
+            Command::none()
+        }
+        Request::Volume { value } => update(launcher, Message::VolumeChanged(value)),
+        Request::Brightness { value } => update(launcher, Message::BrightnessChanged(value)),
+        Request::Media { action } => {
+            if !launcher.music_player.state.player_available {
+                return Err("no media player found".to_string());
+            }
+            match action {
+                MediaAction::PlayPause => update(launcher, Message::MusicPlayPause),
+                MediaAction::Next => update(launcher, Message::MusicNext),
+                MediaAction::Previous => update(launcher, Message::MusicPrevious),
+            }
+        }
+    };
+    Ok(command)
+}
+
+/// What status bars and scripts see in every successful reply
+fn state(launcher: &Launcher) -> Value {
+    let music = &launcher.music_player.state;
+    let wallpaper = launcher
+        .wallpaper_index
+        .as_ref()
+        .and_then(|index| index.wallpapers.get(launcher.wallpaper_selected_index))
+        .map(|entry| entry.name.clone());
+
+    json!({
+        "visible": launcher.surface.is_some(),
+        "panel": launcher.current_panel,
+        "mode": launcher.search_mode,
+        "control_center": launcher.control_center_visible,
+        "clipboard": launcher.clipboard_visible,
+        "volume": launcher.services_panel.volume_value.round(),
+        "brightness": launcher.services_panel.brightness_value.round(),
+        "media": {
+            "available": music.player_available,
+            "playing": music.is_playing,
+            "player": music.app_name,
+            "title": music.song_name,
+            "artist": music.artist_name,
+        },
+        "wallpaper": wallpaper,
+    })
+}
+
+/// Full text of every clipboard entry, newest first
+fn clipboard_history() -> Value {
+    let items = data::search_items("");
+    json!(items.iter().map(|item| item.full_content()).collect::<Vec<_>>())
+}
+
+/// Map a fresh surface with the query and panels back in their initial state
+fn show(launcher: &mut Launcher) -> Command<Message> {
+    if launcher.surface.is_some() {
+        return Command::none();
+    }
+
+    // Sources that change on disk without a watcher are reloaded on every show
+    AppList::refresh_sources();
+    launcher.file_search.refresh_index();
+
+    launcher.search_bar.input_value.clear();
+    launcher.search_mode = SearchMode::Apps;
+    launcher.shell_panel.clear();
+    launcher.app_list.reset_selection();
+    let _ = launcher.app_list.update(app_list::Message::SearchInput(String::new()));
+    launcher.current_panel = Panel::Clock;
+    launcher.clipboard_visible = false;
+    launcher.clipboard_selected_index = 0;
+    launcher.control_center_visible = false;
+    // Focuses the search bar once the surface draws its first frame
+    launcher.is_first_frame = true;
+
+    let id = window::Id::unique();
+    launcher.surface = Some(id);
+    Command::done(Message::ShowSurface {
+        settings: NewLayerShellSettings {
+            size: Some(SIZE),
+            layer: Layer::Top,
+            anchor: Anchor::Bottom,
+            exclusive_zone: None,
+            margin: Some(MARGIN),
+            keyboard_interactivity: KeyboardInteractivity::Exclusive,
+            namespace: Some(crate::namespace()),
+            ..Default::default()
+        },
+        id,
+    })
+}
+
+/// Unmap the surface; the launcher keeps running with everything loaded
+pub fn hide(launcher: &mut Launcher) -> Command<Message> {
+    match launcher.surface.take() {
+        Some(id) => Command::done(Message::HideSurface(id)),
+        None => Command::none(),
+    }
+}
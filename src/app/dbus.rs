@@ -0,0 +1,413 @@
+//! `--daemon`: the `io.github.sierra.Launcher` session bus service.
+//!
+//! Methods are turned into the same requests the control socket takes (see `app::daemon`),
+//! and the properties below are kept in sync from `update` with `PropertiesChanged` signals.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use gio::glib::{self, Variant};
+use gio::glib::variant::ToVariant;
+use gio::{BusNameOwnerFlags, BusType, DBusConnection, DBusMethodInvocation, DBusNodeInfo};
+use iced::futures::channel::mpsc::Sender;
+use serde_json::{json, Value};
+
+use crate::app::daemon::{self, Request};
+use crate::app::message::Message;
+use crate::app::state::Launcher;
+
+const BUS_NAME: &str = "io.github.sierra.Launcher";
+const OBJECT_PATH: &str = "/io/github/sierra/Launcher";
+const INTERFACE: &str = "io.github.sierra.Launcher";
+const ERROR_FAILED: &str = "io.github.sierra.Launcher.Error.Failed";
+const ERROR_INVALID_ARGS: &str = "org.freedesktop.DBus.Error.InvalidArgs";
+
+const INTROSPECTION: &str = r#"
+<node>
+  <interface name="io.github.sierra.Launcher">
+    <method name="Show"/>
+    <method name="Hide"/>
+    <method name="Toggle"/>
+    <method name="ShowPanel">
+      <arg name="panel" type="s" direction="in"/>
+    </method>
+    <method name="ShowMode">
+      <arg name="mode" type="s" direction="in"/>
+    </method>
+    <method name="GetState">
+      <arg name="state" type="a{sv}" direction="out"/>
+    </method>
+    <property name="Visible" type="b" access="read"/>
+    <property name="Panel" type="s" access="read"/>
+    <property name="Mode" type="s" access="read"/>
+    <property name="Volume" type="d" access="readwrite"/>
+    <property name="Brightness" type="d" access="readwrite"/>
+    <property name="Wallpaper" type="s" access="read"/>
+  </interface>
+</node>
+"#;
+
+/// Property values as last published by `update`
+#[derive(Debug, Clone, PartialEq)]
+struct Properties {
+    visible: bool,
+    panel: String,
+    mode: String,
+    volume: f64,
+    brightness: f64,
+    wallpaper: String,
+}
+
+impl Properties {
+    fn of(launcher: &Launcher) -> Self {
+        let name = |value: Value| value.as_str().unwrap_or_default().to_string();
+        Self {
+            visible: launcher.surface.is_some(),
+            panel: name(json!(launcher.current_panel)),
+            mode: name(json!(launcher.search_mode)),
+            volume: launcher.services_panel.volume_value.round() as f64,
+            brightness: launcher.services_panel.brightness_value.round() as f64,
+            wallpaper: launcher
+                .wallpaper_index
+                .as_ref()
+                .and_then(|index| index.wallpapers.get(launcher.wallpaper_selected_index))
+                .map(|entry| entry.name.clone())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// `(name, value)` for every property, in introspection order
+    fn variants(&self) -> [(&'static str, Variant); 6] {
+        [
+            ("Visible", self.visible.to_variant()),
+            ("Panel", self.panel.to_variant()),
+            ("Mode", self.mode.to_variant()),
+            ("Volume", self.volume.to_variant()),
+            ("Brightness", self.brightness.to_variant()),
+            ("Wallpaper", self.wallpaper.to_variant()),
+        ]
+    }
+}
+
+static CONNECTION: OnceLock<DBusConnection> = OnceLock::new();
+static PROPERTIES: Mutex<Option<Properties>> = Mutex::new(None);
+
+/// Own the bus name on the glib main loop thread and forward method calls to `output`
+pub fn start(output: Sender<Message>) {
+    // `spawn` rather than `invoke`: the closures below must be created on the thread running the loop
+    glib::MainContext::default().spawn(async move {
+        let registered = output.clone();
+        gio::bus_own_name(
+            BusType::Session,
+            BUS_NAME,
+            BusNameOwnerFlags::NONE,
+            move |connection, _| register(&connection, registered.clone()),
+            |_, name| eprintln!("[DBus] Acquired {}", name),
+            |connection, name| match connection {
+                Some(_) => eprintln!("[DBus] {} is owned by another process", name),
+                None => eprintln!("[DBus] No session bus, {} not registered", name),
+            },
+        );
+    });
+}
+
+fn register(connection: &DBusConnection, output: Sender<Message>) {
+    let interface = DBusNodeInfo::for_xml(INTROSPECTION)
+        .ok()
+        .and_then(|node| node.lookup_interface(INTERFACE))
+        .expect("introspection XML is valid");
+
+    let setter = output.clone();
+    let registration = connection
+        .register_object(OBJECT_PATH, &interface)
+        .method_call(move |_, _, _, _, method, parameters, invocation| {
+            call(method, &parameters, invocation, output.clone());
+        })
+        .property(|_, _, _, _, property| property_value(property))
+        .set_property(move |_, _, _, _, property, value| set_property(property, &value, setter.clone()))
+        .build();
+
+    match registration {
+        Ok(_) => {
+            let _ = CONNECTION.set(connection.clone());
+        }
+        Err(e) => eprintln!("[DBus] Can't register {}: {}", OBJECT_PATH, e),
+    }
+}
+
+/// Answer a method call once `update` has handled it, without blocking the main loop
+fn call(method: &str, parameters: &Variant, invocation: DBusMethodInvocation, mut output: Sender<Message>) {
+    let request = match method_request(method, parameters) {
+        Ok(request) => request,
+        Err(e) => return invocation.return_dbus_error(ERROR_INVALID_ARGS, &e),
+    };
+
+    let returns_state = request == Request::State;
+    glib::MainContext::default().spawn_local(async move {
+        let reply = gio::spawn_blocking(move || daemon::dispatch(&mut output, request))
+            .await
+            .unwrap_or_else(|_| Err("launcher did not answer".to_string()));
+
+        match reply {
+            Ok(answer) if returns_state => {
+                let state = answer.state.as_object().map(dict).unwrap_or_else(|| dict(&Default::default()));
+                invocation.return_value(Some(&Variant::tuple_from_iter([state])))
+            }
+            Ok(_) => invocation.return_value(None),
+            Err(e) => invocation.return_dbus_error(ERROR_FAILED, &e),
+        }
+    });
+}
+
+/// `a{sv}` for a JSON object; nulls are left out, as D-Bus has no null
+fn dict(object: &serde_json::Map<String, Value>) -> Variant {
+    object
+        .iter()
+        .filter_map(|(key, value)| Some((key.clone(), json_variant(value)?)))
+        .collect::<HashMap<String, Variant>>()
+        .to_variant()
+}
+
+/// JSON as a variant: numbers become doubles, arrays `av` and objects `a{sv}`
+fn json_variant(value: &Value) -> Option<Variant> {
+    Some(match value {
+        Value::Null => return None,
+        Value::Bool(value) => value.to_variant(),
+        Value::Number(value) => value.as_f64()?.to_variant(),
+        Value::String(value) => value.to_variant(),
+        Value::Array(items) => items.iter().filter_map(json_variant).collect::<Vec<_>>().to_variant(),
+        Value::Object(object) => dict(object),
+    })
+}
+
+/// The control socket request a method stands for
+fn method_request(method: &str, parameters: &Variant) -> Result<Request, String> {
+    let argument = || {
+        parameters
+            .get::<(String,)>()
+            .map(|(argument,)| argument)
+            .ok_or_else(|| format!("{} takes one string argument", method))
+    };
+
+    let request = match method {
+        "Show" => json!({ "command": "show" }),
+        "Hide" => json!({ "command": "hide" }),
+        "Toggle" => json!({ "command": "toggle" }),
+        "GetState" => json!({ "command": "state" }),
+        "ShowPanel" => json!({ "command": "panel", "panel": argument()? }),
+        "ShowMode" => json!({ "command": "mode", "mode": argument()? }),
+        _ => return Err(format!("unknown method {}", method)),
+    };
+    serde_json::from_value(request).map_err(|e| e.to_string())
+}
+
+fn property_value(property: &str) -> Variant {
+    let properties = PROPERTIES.lock().unwrap();
+    let Some(properties) = properties.as_ref() else {
+        // Only until the first `update`; the types still have to match the introspection data
+        return match property {
+            "Visible" => false.to_variant(),
+            "Volume" | "Brightness" => 0.0f64.to_variant(),
+            _ => "".to_variant(),
+        };
+    };
+
+    properties
+        .variants()
+        .into_iter()
+        .find(|(name, _)| *name == property)
+        .map(|(_, value)| value)
+        .unwrap_or_else(|| "".to_variant())
+}
+
+/// Volume and Brightness can be set; the change signal follows once `update` applies it.
+/// GDBus wants the outcome right away, so this waits (a second at most) for `update` on the
+/// glib thread; a failed set is an error for the caller.
+fn set_property(property: &str, value: &Variant, mut output: Sender<Message>) -> bool {
+    let Some(value) = value.get::<f64>() else {
+        return false;
+    };
+    let request = match property {
+        "Volume" => Request::Volume { value: value as f32 },
+        "Brightness" => Request::Brightness { value: value as f32 },
+        _ => return false,
+    };
+    match daemon::dispatch(&mut output, request) {
+        Ok(_) => true,
+        Err(e) => {
+            eprintln!("[DBus] Can't set {}: {}", property, e);
+            false
+        }
+    }
+}
+
+/// Record the launcher's properties and signal the ones that changed since the last call
+pub fn publish(launcher: &Launcher) {
+    let current = Properties::of(launcher);
+    let previous = {
+        let mut published = PROPERTIES.lock().unwrap();
+        if published.as_ref() == Some(&current) {
+            return;
+        }
+        published.replace(current.clone())
+    };
+
+    let Some(connection) = CONNECTION.get() else {
+        return;
+    };
+    let before = previous.map(|previous| previous.variants());
+    let changed: HashMap<String, Variant> = current
+        .variants()
+        .into_iter()
+        .filter(|property| before.as_ref().is_none_or(|before| !before.contains(property)))
+        .map(|(name, value)| (name.to_string(), value))
+        .collect();
+
+    let parameters = (INTERFACE, changed, Vec::<String>::new()).to_variant();
+    if let Err(e) = connection.emit_signal(
+        None,
+        OBJECT_PATH,
+        "org.freedesktop.DBus.Properties",
+        "PropertiesChanged",
+        Some(&parameters),
+    ) {
+        eprintln!("[DBus] Can't signal property changes: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::daemon::Answer;
+    use crate::app::state::Panel;
+    use gio::{DBusCallFlags, DBusConnectionFlags, TestDBus, TestDBusFlags};
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    /// Stands in for `update`: records requests and answers them, failing Brightness
+    fn fake_launcher() -> (Sender<Message>, mpsc::Receiver<Request>) {
+        let (output, mut messages) = iced::futures::channel::mpsc::channel(8);
+        let (seen, requests) = mpsc::channel();
+        thread::spawn(move || loop {
+            match messages.try_next() {
+                Ok(Some(Message::Daemon(request, reply))) => {
+                    let answer = match request {
+                        Request::Brightness { .. } => Err("no backlight".to_string()),
+                        _ => Ok(Answer {
+                            state: json!({
+                                "visible": true,
+                                "volume": 40.0,
+                                "media": { "playing": false, "player": "mpv" },
+                                "wallpaper": null,
+                            }),
+                            result: None,
+                        }),
+                    };
+                    reply.send(answer);
+                    let _ = seen.send(request);
+                }
+                Ok(Some(_)) => {}
+                Ok(None) => return,
+                Err(_) => thread::sleep(Duration::from_millis(5)),
+            }
+        });
+        (output, requests)
+    }
+
+    fn connect(address: &str) -> DBusConnection {
+        DBusConnection::for_address_sync(
+            address,
+            DBusConnectionFlags::AUTHENTICATION_CLIENT | DBusConnectionFlags::MESSAGE_BUS_CONNECTION,
+            None,
+            gio::Cancellable::NONE,
+        )
+        .expect("private bus accepts connections")
+    }
+
+    fn call(
+        client: &DBusConnection,
+        interface: &str,
+        method: &str,
+        parameters: Option<Variant>,
+    ) -> Result<Variant, glib::Error> {
+        client.call_sync(
+            Some(BUS_NAME),
+            OBJECT_PATH,
+            interface,
+            method,
+            parameters.as_ref(),
+            None,
+            DBusCallFlags::NONE,
+            5000,
+            gio::Cancellable::NONE,
+        )
+    }
+
+    #[test]
+    fn service_on_a_private_bus() {
+        let on_path = std::env::var_os("PATH")
+            .is_some_and(|path| std::env::split_paths(&path).any(|dir| dir.join("dbus-daemon").is_file()));
+        if !on_path {
+            eprintln!("dbus-daemon not found, skipping");
+            return;
+        }
+        let bus = TestDBus::new(TestDBusFlags::NONE);
+        bus.up();
+        let address = bus.bus_address().expect("bus is up").to_string();
+
+        let (output, requests) = fake_launcher();
+        let (ready, registered) = mpsc::channel();
+        let service_address = address.clone();
+        thread::spawn(move || {
+            // `call` answers from the default context, like under the daemon's main loop
+            let context = glib::MainContext::default();
+            let _guard = context.acquire().expect("default context is free");
+            let connection = connect(&service_address);
+            register(&connection, output);
+            connection
+                .call_sync(
+                    Some("org.freedesktop.DBus"),
+                    "/org/freedesktop/DBus",
+                    "org.freedesktop.DBus",
+                    "RequestName",
+                    Some(&(BUS_NAME, 0u32).to_variant()),
+                    None,
+                    DBusCallFlags::NONE,
+                    5000,
+                    gio::Cancellable::NONE,
+                )
+                .expect("name is free");
+            ready.send(()).unwrap();
+            glib::MainLoop::new(Some(&context), false).run();
+        });
+        registered.recv_timeout(Duration::from_secs(5)).expect("service registers");
+        let client = connect(&address);
+
+        let reply = call(&client, INTERFACE, "GetState", None).expect("GetState succeeds");
+        assert_eq!(reply.type_().as_str(), "(a{sv})");
+        let state: HashMap<String, Variant> = reply.child_value(0).get().unwrap();
+        assert_eq!(state["visible"].get::<bool>(), Some(true));
+        assert_eq!(state["volume"].get::<f64>(), Some(40.0));
+        let media: HashMap<String, Variant> = state["media"].get().unwrap();
+        assert_eq!(media["player"].get::<String>().as_deref(), Some("mpv"));
+        assert!(!state.contains_key("wallpaper"));
+        assert_eq!(requests.recv().unwrap(), Request::State);
+
+        call(&client, INTERFACE, "ShowPanel", Some(("music",).to_variant())).expect("ShowPanel succeeds");
+        assert_eq!(requests.recv().unwrap(), Request::Panel { panel: Panel::Music });
+        let error = call(&client, INTERFACE, "ShowPanel", Some(("nowhere",).to_variant())).unwrap_err();
+        assert!(error.message().contains("InvalidArgs"), "{}", error);
+
+        let set = |property: &str, value: f64| {
+            let parameters = (INTERFACE, property, value.to_variant()).to_variant();
+            call(&client, "org.freedesktop.DBus.Properties", "Set", Some(parameters))
+        };
+        set("Volume", 55.0).expect("setting Volume succeeds");
+        assert_eq!(requests.recv().unwrap(), Request::Volume { value: 55.0 });
+        assert!(set("Brightness", 10.0).is_err(), "a failed set reaches the caller");
+        assert!(set("Panel", 1.0).is_err(), "Panel is read-only");
+
+        bus.down();
+    }
+}
@@ -1,7 +1,10 @@
 use iced::Event;
-use iced_layershell::actions::LayershellCustomActionWithId;
+use iced::window;
+use iced_layershell::actions::{LayershellCustomAction, LayershellCustomActionWithId};
+use iced_layershell::reexport::NewLayerShellSettings;
 use crate::panels::{search_bar, app_list};
-use crate::app::state::Direction;
+use crate::app::daemon::{Reply, Request};
+use crate::app::state::{Direction, Panel};
 
 #[derive(Debug, Clone)]
 pub enum Message {
@@ -10,6 +13,7 @@ pub enum Message {
     SearchBarMessage(search_bar::Message),
     AppListMessage(app_list::Message),
     CyclePanel(Direction),
+    SetPanel(Panel),
     MusicPlayPause,
     MusicNext,
     MusicPrevious,
@@ -35,11 +39,27 @@ pub enum Message {
     NextWallpaper,
     NoOp,
     SetWallpaper(usize),
+    /// Request received on the `--daemon` control socket
+    Daemon(Request, Reply),
+    /// Map the launcher surface (`--daemon` only)
+    ShowSurface { settings: NewLayerShellSettings, id: window::Id },
+    /// Unmap the launcher surface (`--daemon` only)
+    HideSurface(window::Id),
 }
 
 impl TryInto<LayershellCustomActionWithId> for Message {
     type Error = Self;
     fn try_into(self) -> Result<LayershellCustomActionWithId, Self::Error> {
-        Err(self)
+        match self {
+            Self::ShowSurface { settings, id } => Ok(LayershellCustomActionWithId::new(
+                None,
+                LayershellCustomAction::NewLayerShell { settings, id },
+            )),
+            Self::HideSurface(id) => Ok(LayershellCustomActionWithId::new(
+                Some(id),
+                LayershellCustomAction::RemoveWindow,
+            )),
+            _ => Err(self),
+        }
     }
 }
@@ -3,3 +3,5 @@ pub mod message;
 pub mod update;
 pub mod view;
 pub mod subscription;
+pub mod daemon;
+pub mod dbus;
@@ -10,10 +10,19 @@ use crate::panels::mpris_player::MusicPlayer;
 use crate::panels::system::SystemPanel;
 use crate::panels::services::ServicesPanel;
 use crate::panels::weather::WeatherPanel;
+use crate::panels::shell::ShellPanel;
+use crate::panels::calculator::CalculatorPanel;
+use crate::panels::file_search::FileSearchPanel;
+use crate::panels::recent::RecentPanel;
+use crate::panels::symbols::SymbolPanel;
+use crate::panels::windows::WindowPanel;
+use iced::keyboard::Modifiers;
+use serde::{Deserialize, Serialize};
 
 use std::time::Instant;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Panel {
     Clock,
     Weather,
@@ -23,12 +32,77 @@ pub enum Panel {
     Services,
 }
 
+/// What the search bar query is routed to, picked by its prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchMode {
+    Apps,
+    /// `> command` runs through `$SHELL -c`
+    Shell,
+    /// `= expression` evaluates arithmetic
+    Calculator,
+    /// `/ name` searches files and folders in the home directory
+    Files,
+    /// `~ name` lists recently used documents
+    Recent,
+    /// `: name` picks an emoji or Unicode symbol
+    Symbols,
+    /// `! title` switches to an open window
+    Windows,
+}
+
+impl SearchMode {
+    /// Mode selected by `query`, and the query with the mode prefix removed
+    pub fn from_query(query: &str) -> (Self, &str) {
+        if let Some(command) = query.strip_prefix('>') {
+            return (SearchMode::Shell, command.trim_start());
+        }
+        if let Some(expression) = query.strip_prefix('=') {
+            return (SearchMode::Calculator, expression.trim_start());
+        }
+        if let Some(name) = query.strip_prefix('/') {
+            return (SearchMode::Files, name.trim_start());
+        }
+        if let Some(name) = query.strip_prefix('~') {
+            return (SearchMode::Recent, name.trim_start());
+        }
+        if let Some(name) = query.strip_prefix(':') {
+            return (SearchMode::Symbols, name.trim_start());
+        }
+        if let Some(title) = query.strip_prefix('!') {
+            return (SearchMode::Windows, title.trim_start());
+        }
+        (SearchMode::Apps, query)
+    }
+
+    /// Character that selects this mode at the start of the query
+    pub fn prefix(self) -> Option<char> {
+        match self {
+            SearchMode::Apps => None,
+            SearchMode::Shell => Some('>'),
+            SearchMode::Calculator => Some('='),
+            SearchMode::Files => Some('/'),
+            SearchMode::Recent => Some('~'),
+            SearchMode::Symbols => Some(':'),
+            SearchMode::Windows => Some('!'),
+        }
+    }
+}
+
 pub struct Launcher {
     pub theme: Theme,
     pub watcher: Option<ColorWatcher>,
     pub config: Config,
     pub search_bar: SearchBar,
     pub app_list: AppList,
+    pub search_mode: SearchMode,
+    pub shell_panel: ShellPanel,
+    pub calculator_panel: CalculatorPanel,
+    pub file_search: FileSearchPanel,
+    pub recent_panel: RecentPanel,
+    pub symbol_panel: SymbolPanel,
+    pub window_panel: WindowPanel,
+    pub modifiers: Modifiers,
     pub current_panel: Panel,
     pub weather_panel: WeatherPanel,
     pub music_player: MusicPlayer,
@@ -44,6 +118,10 @@ pub struct Launcher {
     pub is_first_frame: bool,
     pub wallpaper_index: Option<WallpaperIndex>,
     pub wallpaper_selected_index: usize,
+    /// Resident `--daemon` launcher: closing hides instead of exiting
+    pub daemon: bool,
+    /// Mapped surface of a `--daemon` launcher, `None` while hidden
+    pub surface: Option<iced::window::Id>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
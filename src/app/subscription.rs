@@ -1,10 +1,41 @@
+use iced::futures::Stream;
 use iced::{Subscription, window, event};
+use crate::app::daemon::{self, Request};
+use crate::app::dbus;
 use crate::app::message::Message;
+use crate::app::state::Launcher;
+use crate::utils::ipc;
 
-pub fn subscription() -> Subscription<Message> {
+pub fn subscription(launcher: &Launcher) -> Subscription<Message> {
     let events = event::listen().map(Message::IcedEvent);
     let frames = window::frames().map(|_| Message::CheckColors);
     let music_refresh = window::frames().map(|_| Message::MusicRefresh);
 
-    Subscription::batch(vec![events, frames, music_refresh])
+    let mut subscriptions = vec![events, frames, music_refresh];
+    if launcher.daemon {
+        subscriptions.push(Subscription::run(daemon_commands));
+        subscriptions.push(Subscription::run(dbus_calls));
+    }
+    Subscription::batch(subscriptions)
+}
+
+/// Requests on the control socket, served from a blocking thread
+fn daemon_commands() -> impl Stream<Item = Message> {
+    iced::stream::channel(16, async |output| {
+        let Some(listener) = ipc::take_listener() else {
+            return;
+        };
+        std::thread::spawn(move || {
+            ipc::serve(listener, move |line| {
+                let mut output = output.clone();
+                let reply = Request::parse(line).and_then(|request| daemon::dispatch(&mut output, request));
+                daemon::reply_line(reply)
+            });
+        });
+    })
+}
+
+/// Method calls on the session bus service, answered from the glib main loop
+fn dbus_calls() -> impl Stream<Item = Message> {
+    iced::stream::channel(16, async |output| dbus::start(output))
 }
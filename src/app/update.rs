@@ -5,9 +5,11 @@ use iced::keyboard;
 use keyboard::key::Named;
 use iced::mouse;
 
-use crate::app::state::{Launcher, Panel, Direction};
+use crate::app::daemon;
+use crate::app::state::{Launcher, Panel, Direction, SearchMode};
 use crate::app::message::Message;
 use crate::panels::{search_bar, app_list};
+use crate::utils::dmenu;
 use crate::utils::theme::WalColors;
 use std::time::{Duration, Instant};
 
@@ -18,16 +20,17 @@ pub fn update(launcher: &mut Launcher, message: Message) -> Command<Message> {
                 Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) => {
                     match key {
                         keyboard::Key::Named(Named::Escape) => {
-                            std::process::exit(0);
+                            if launcher.app_list.is_dmenu() {
+                                dmenu::cancel();
+                            }
+                            return close(launcher);
                         }
                         
                         keyboard::Key::Named(Named::Enter) => {
                             if launcher.clipboard_visible {
                                 return Command::perform(async {}, |_| Message::ClipboardSelect);
                             } else {
-                                // Launch selected app
-                                let _ = launcher.app_list.update(app_list::Message::LaunchSelected);
-                                std::process::exit(0);
+                                return submit_query(launcher, modifiers);
                             }
                         }
                         
@@ -35,7 +38,17 @@ pub fn update(launcher: &mut Launcher, message: Message) -> Command<Message> {
                             if launcher.clipboard_visible {
                                 return Command::perform(async {}, |_| Message::ClipboardArrowUp);
                             } else {
-                                let _ = launcher.app_list.update(app_list::Message::ArrowUp);
+                                match launcher.search_mode {
+                                    SearchMode::Apps => {
+                                        let _ = launcher.app_list.update(app_list::Message::ArrowUp);
+                                    }
+                                    SearchMode::Calculator => launcher.calculator_panel.arrow_up(),
+                                    SearchMode::Files => launcher.file_search.arrow_up(),
+                                    SearchMode::Recent => launcher.recent_panel.arrow_up(),
+                                    SearchMode::Symbols => launcher.symbol_panel.arrow_up(),
+                                    SearchMode::Windows => launcher.window_panel.arrow_up(),
+                                    SearchMode::Shell => {}
+                                }
                             }
                         }
                         
@@ -43,7 +56,17 @@ pub fn update(launcher: &mut Launcher, message: Message) -> Command<Message> {
                             if launcher.clipboard_visible {
                                 return Command::perform(async {}, |_| Message::ClipboardArrowDown);
                             } else {
-                                let _ = launcher.app_list.update(app_list::Message::ArrowDown);
+                                match launcher.search_mode {
+                                    SearchMode::Apps => {
+                                        let _ = launcher.app_list.update(app_list::Message::ArrowDown);
+                                    }
+                                    SearchMode::Calculator => launcher.calculator_panel.arrow_down(),
+                                    SearchMode::Files => launcher.file_search.arrow_down(),
+                                    SearchMode::Recent => launcher.recent_panel.arrow_down(),
+                                    SearchMode::Symbols => launcher.symbol_panel.arrow_down(),
+                                    SearchMode::Windows => launcher.window_panel.arrow_down(),
+                                    SearchMode::Shell => {}
+                                }
                             }
                         }
                         
@@ -63,11 +86,29 @@ pub fn update(launcher: &mut Launcher, message: Message) -> Command<Message> {
                             }
                         }
 
+                        keyboard::Key::Named(Named::Tab) if !launcher.clipboard_visible => {
+                            match launcher.search_mode {
+                                // dmenu completes the input with the selected line
+                                SearchMode::Apps if launcher.app_list.is_dmenu() => {
+                                    if let Some(line) = launcher.app_list.selected_line() {
+                                        launcher.search_bar.input_value = line.to_string();
+                                        route_query(launcher);
+                                    }
+                                }
+                                SearchMode::Apps => {
+                                    let _ = launcher.app_list.update(app_list::Message::ToggleActions);
+                                }
+                                SearchMode::Files => launcher.file_search.toggle_actions(),
+                                SearchMode::Symbols => launcher.symbol_panel.toggle_variants(),
+                                SearchMode::Shell | SearchMode::Calculator | SearchMode::Recent | SearchMode::Windows => {}
+                            }
+                        }
+
                         keyboard::Key::Named(Named::Backspace) => {
                             if !launcher.clipboard_visible && !launcher.search_bar.input_value.is_empty() {
                                 // Handle backspace for search input
                                 launcher.search_bar.input_value.pop();
-                                let _ = launcher.app_list.update(app_list::Message::SearchInput(launcher.search_bar.input_value.clone()));
+                                route_query(launcher);
                             }
                         }
 
@@ -77,13 +118,16 @@ pub fn update(launcher: &mut Launcher, message: Message) -> Command<Message> {
                             } else if !launcher.clipboard_visible && !modifiers.control() && !modifiers.alt() && !modifiers.logo() {
                                 // Type into search bar even when not focused
                                 launcher.search_bar.input_value.push_str(c.as_str());
-                                let _ = launcher.app_list.update(app_list::Message::SearchInput(launcher.search_bar.input_value.clone()));
+                                route_query(launcher);
                             }
                         }
                         
                         _ => {}
                     }
                 }
+                Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                    launcher.modifiers = modifiers;
+                }
                 Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Right)) => {
                     launcher.control_center_visible = !launcher.control_center_visible;
                 }
@@ -109,6 +153,13 @@ pub fn update(launcher: &mut Launcher, message: Message) -> Command<Message> {
             if launcher.app_list.check_loaded() {
                 eprintln!("[Main] Apps finished loading - UI will update automatically");
             }
+            launcher.app_list.check_plugins();
+            match launcher.search_mode {
+                SearchMode::Files => launcher.file_search.check_index(),
+                SearchMode::Recent => launcher.recent_panel.check_loaded(),
+                SearchMode::Windows => launcher.window_panel.check_loaded(),
+                _ => {}
+            }
             
             launcher.frame_count += 1;
             
@@ -145,17 +196,23 @@ pub fn update(launcher: &mut Launcher, message: Message) -> Command<Message> {
         Message::SearchBarMessage(search_bar_message) => {
             match search_bar_message {
                 search_bar::Message::InputChanged(value) => {
-                    launcher.search_bar.input_value = value.clone();
-                    let _ = launcher.app_list.update(app_list::Message::SearchInput(value));
+                    launcher.search_bar.input_value = value;
+                    route_query(launcher);
                     Command::none()
                 }
-                search_bar::Message::Submitted => {
-                    let _ = launcher.app_list.update(app_list::Message::LaunchSelected);
-                    std::process::exit(0);
-                }
+                search_bar::Message::Submitted => submit_query(launcher, launcher.modifiers),
             }
         }
         
+        Message::AppListMessage(app_list_message @ app_list::Message::PluginActivated { .. }) => {
+            let _ = launcher.app_list.update(app_list_message);
+            // A one-shot launcher stays up until the plugin is done, see `submit_query`
+            if !launcher.daemon {
+                std::process::exit(0);
+            }
+            Command::none()
+        }
+
         Message::AppListMessage(app_list_message) => {
             let _ = launcher.app_list.update(app_list_message);
             Command::none()
@@ -184,6 +241,14 @@ pub fn update(launcher: &mut Launcher, message: Message) -> Command<Message> {
             Command::none()
         }
 
+        Message::SetPanel(panel) => {
+            launcher.current_panel = panel;
+            if panel == Panel::Services {
+                launcher.services_panel.schedule_refresh();
+            }
+            Command::none()
+        }
+
         Message::MusicPlayPause => {
             launcher.music_player.play_pause();
             Command::none()
@@ -282,9 +347,10 @@ pub fn update(launcher: &mut Launcher, message: Message) -> Command<Message> {
             let _ = std::process::Command::new("bash")
                 .arg("-c")
                 .arg("(sleep 0.5 && systemctl suspend) &")
-                .spawn();
+                .spawn()
+                .map(crate::utils::terminal::reap_in_background);
             
-            std::process::exit(0);
+            close(launcher)
         }
 
         Message::ClipboardArrowUp => {
@@ -365,5 +431,106 @@ pub fn update(launcher: &mut Launcher, message: Message) -> Command<Message> {
         }
 
         Message::NoOp => Command::none(),
+
+        Message::Daemon(request, reply) => daemon::handle(launcher, request, reply),
+        // Turned into layer shell actions by the runtime before reaching `update`
+        Message::ShowSurface { .. } | Message::HideSurface(_) => Command::none(),
+    }
+}
+
+/// Esc or a finished action: a `--daemon` launcher hides, a one-shot one exits
+fn close(launcher: &mut Launcher) -> Command<Message> {
+    // A `> command` still running would outlive the launcher
+    launcher.shell_panel.clear();
+    if !launcher.daemon {
+        std::process::exit(0);
+    }
+    daemon::hide(launcher)
+}
+
+/// Mode the search bar text selects and the query for it; `--dmenu` only ever filters its lines
+fn split_query(launcher: &Launcher) -> (SearchMode, String) {
+    if launcher.app_list.is_dmenu() {
+        return (SearchMode::Apps, launcher.search_bar.input_value.clone());
+    }
+    let (mode, query) = SearchMode::from_query(&launcher.search_bar.input_value);
+    (mode, query.to_string())
+}
+
+/// Send the search bar text to the mode its prefix selects
+fn route_query(launcher: &mut Launcher) {
+    let (mode, query) = split_query(launcher);
+
+    if mode != launcher.search_mode {
+        if launcher.search_mode == SearchMode::Shell {
+            launcher.shell_panel.clear();
+        }
+        // Windows come and go, so list them fresh each time the mode is entered
+        if mode == SearchMode::Windows {
+            launcher.window_panel.refresh();
+        }
+        launcher.search_mode = mode;
+    }
+
+    match mode {
+        SearchMode::Apps => {
+            let _ = launcher.app_list.update(app_list::Message::SearchInput(query));
+        }
+        // Shell commands only run on Enter
+        SearchMode::Shell => {}
+        SearchMode::Calculator => launcher.calculator_panel.set_expression(&query),
+        SearchMode::Files => launcher.file_search.set_query(&query),
+        SearchMode::Recent => launcher.recent_panel.set_query(&query),
+        SearchMode::Symbols => launcher.symbol_panel.set_query(&query),
+        SearchMode::Windows => launcher.window_panel.set_query(&query),
+    }
+}
+
+/// Enter in the search bar; closes the launcher unless the mode shows its result in place
+fn submit_query(launcher: &mut Launcher, modifiers: keyboard::Modifiers) -> Command<Message> {
+    let (mode, query) = split_query(launcher);
+
+    match mode {
+        // Shift+Enter (or nothing matching) returns the typed text, like dmenu
+        SearchMode::Apps if launcher.app_list.is_dmenu() => match launcher.app_list.selected_line() {
+            Some(line) if !modifiers.shift() => dmenu::select(line),
+            _ => dmenu::select(&query),
+        },
+        // Exiting right away would drop the item before the plugin worker hands it over
+        SearchMode::Apps if launcher.app_list.is_plugin_selected() && !launcher.daemon => launcher
+            .app_list
+            .update(app_list::Message::LaunchSelected)
+            .map(Message::AppListMessage),
+        SearchMode::Apps => {
+            let launched = launcher.app_list.update(app_list::Message::LaunchSelected);
+            Command::batch([launched.map(Message::AppListMessage), close(launcher)])
+        }
+        SearchMode::Shell if modifiers.control() => {
+            launcher.shell_panel.run_detached(&query);
+            close(launcher)
+        }
+        SearchMode::Shell => {
+            launcher.shell_panel.run(&query);
+            Command::none()
+        }
+        SearchMode::Calculator => {
+            launcher.calculator_panel.set_expression(&query);
+            let Some(value) = launcher.calculator_panel.take_selected() else {
+                return Command::none();
+            };
+            if launcher.config.calculator_skip_history {
+                crate::utils::monitor::set_ignore_next(value.clone());
+            }
+            if let Err(e) = crate::utils::copy::copy_to_clipboard(&value) {
+                eprintln!("[Calculator] Failed to copy result: {}", e);
+                return Command::none();
+            }
+            close(launcher)
+        }
+        SearchMode::Files if launcher.file_search.activate() => close(launcher),
+        SearchMode::Recent if launcher.recent_panel.activate() => close(launcher),
+        SearchMode::Symbols if launcher.symbol_panel.activate() => close(launcher),
+        SearchMode::Windows if launcher.window_panel.activate() => close(launcher),
+        SearchMode::Files | SearchMode::Recent | SearchMode::Symbols | SearchMode::Windows => Command::none(),
     }
 }
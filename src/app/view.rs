@@ -1,7 +1,7 @@
 use iced::widget::{container, text, stack, row, column};
 use iced::{Element, Border, Color, Length};
 
-use crate::app::state::Launcher;
+use crate::app::state::{Launcher, SearchMode};
 use crate::app::message::Message;
 use crate::panels::right_main_panels::right_main_panels_view;
 
@@ -29,6 +29,20 @@ pub fn view(launcher: &Launcher) -> Element<'_, Message> {
         );
     }
 
+    // The results area shows whatever the search mode produces
+    let results = match launcher.search_mode {
+        SearchMode::Apps => launcher
+            .app_list
+            .view(&launcher.theme, font, font_size)
+            .map(Message::AppListMessage),
+        SearchMode::Shell => launcher.shell_panel.view(&launcher.theme, font, font_size),
+        SearchMode::Calculator => launcher.calculator_panel.view(&launcher.theme, font, font_size),
+        SearchMode::Files => launcher.file_search.view(&launcher.theme, font, font_size),
+        SearchMode::Recent => launcher.recent_panel.view(&launcher.theme, font, font_size),
+        SearchMode::Symbols => launcher.symbol_panel.view(&launcher.theme, font, font_size),
+        SearchMode::Windows => launcher.window_panel.view(&launcher.theme, font, font_size),
+    };
+
     container(
         stack![
             container(
@@ -64,7 +78,7 @@ pub fn view(launcher: &Launcher) -> Element<'_, Message> {
                         font,
                         font_size,
                         &launcher.search_bar,
-                        &launcher.app_list,
+                        results,
                         launcher.current_panel,
                         &launcher.weather_panel,
                         &launcher.music_player,
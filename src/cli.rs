@@ -0,0 +1,370 @@
+//! Command line: startup options for the launcher, and subcommands that work on the caches
+//! without opening a window, through the daemon when one is running.
+
+use std::io;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+
+use crate::app::state::{Panel, SearchMode};
+use crate::config::Config;
+use crate::utils::data;
+use crate::utils::ipc;
+use crate::utils::wallpaper_manager::WallpaperManager;
+
+pub const USAGE: &str = "\
+Usage: sierra-launcher [OPTIONS] [COMMAND]
+
+Options:
+  --panel <PANEL>      Start on a panel: clock, weather, music, wallpaper, system, services
+  --clipboard          Start with the clipboard history open
+  --mode <MODE>        Start in a search mode: apps, shell, calculator, files, recent, symbols, windows
+  -c, --config <PATH>  Read the config from PATH instead of ~/.config/sierra/Sierra
+  --daemon             Stay resident and show the launcher on `toggle`
+  --dmenu              Pick one of the lines read from stdin and print it
+  -p, --prompt <TEXT>  Search bar placeholder for --dmenu
+  -V, --version        Print the version
+  -h, --help           Print this help
+
+Commands:
+  toggle | show | hide | state   Control a running daemon
+  clipboard list                 List the clipboard history, newest first
+  clipboard get <N>              Print entry N of the history
+  clipboard clear                Forget the clipboard history
+  wallpaper next | previous      Switch to the next or previous wallpaper
+  wallpaper set <NAME>           Switch to the wallpaper with file name NAME
+";
+
+/// Exit status for a command line that can't be parsed
+pub const EXIT_USAGE: i32 = 2;
+
+/// Width of the previews in `clipboard list`
+const PREVIEW_CHARS: usize = 80;
+
+/// How the launcher comes up, from `--panel`, `--clipboard` and `--mode`
+#[derive(Debug, Clone, Default)]
+pub struct Startup {
+    pub panel: Option<Panel>,
+    pub clipboard: bool,
+    pub mode: Option<SearchMode>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Invocation {
+    /// Open the launcher, or keep it resident with `daemon`
+    Launch {
+        startup: Startup,
+        daemon: bool,
+        /// `Some(prompt)` with `--dmenu`
+        dmenu: Option<Option<String>>,
+    },
+    /// Forward a request to the running daemon
+    Daemon(String),
+    Clipboard(ClipboardCommand),
+    Wallpaper(WallpaperCommand),
+    Version,
+    Help,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipboardCommand {
+    List,
+    /// 1-based, as printed by `list`
+    Get(usize),
+    Clear,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WallpaperCommand {
+    Next,
+    Previous,
+    Set(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct Cli {
+    pub config: Option<PathBuf>,
+    pub invocation: Invocation,
+}
+
+static STARTUP: OnceLock<Startup> = OnceLock::new();
+
+pub fn set_startup(startup: Startup) {
+    let _ = STARTUP.set(startup);
+}
+
+pub fn startup() -> Startup {
+    STARTUP.get().cloned().unwrap_or_default()
+}
+
+/// Parse `args` without the program name
+pub fn parse(args: &[String]) -> Result<Cli, String> {
+    let mut config = None;
+    let mut startup = Startup::default();
+    let mut daemon = false;
+    let mut dmenu = false;
+    let mut prompt = None;
+    let mut version = false;
+    let mut help = false;
+    let mut positional = Vec::new();
+    // Options that only shape a launch, which commands must not silently ignore
+    let mut launch_flags = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| args.next().cloned())
+                .ok_or_else(|| format!("{} needs a value", flag))
+        };
+
+        if matches!(flag, "--panel" | "--mode" | "--clipboard" | "--daemon" | "--dmenu" | "-p" | "--prompt") {
+            launch_flags.push(flag);
+        }
+        match flag {
+            "-c" | "--config" => config = Some(PathBuf::from(value()?)),
+            "--panel" => startup.panel = Some(parse_name(flag, &value()?)?),
+            "--mode" => startup.mode = Some(parse_name(flag, &value()?)?),
+            "--clipboard" => startup.clipboard = true,
+            "--daemon" => daemon = true,
+            "--dmenu" => dmenu = true,
+            "-p" | "--prompt" => prompt = Some(value()?),
+            "-V" | "--version" => version = true,
+            "-h" | "--help" => help = true,
+            _ if flag.starts_with('-') && flag.len() > 1 => {
+                return Err(format!("unknown option {}", flag));
+            }
+            _ => positional.push(arg.as_str()),
+        }
+    }
+
+    let invocation = if help {
+        Invocation::Help
+    } else if version {
+        Invocation::Version
+    } else if let Some((&command, rest)) = positional.split_first() {
+        if let Some(flag) = launch_flags.first() {
+            return Err(format!("{} doesn't apply to the {} command", flag, command));
+        }
+        parse_command(command, rest)?
+    } else {
+        let shapes_window = startup.panel.is_some() || startup.mode.is_some() || startup.clipboard;
+        if daemon && dmenu {
+            return Err("--daemon and --dmenu can't be combined".to_string());
+        }
+        if prompt.is_some() && !dmenu {
+            return Err("--prompt only applies to --dmenu".to_string());
+        }
+        if daemon && shapes_window {
+            return Err("--panel, --mode and --clipboard don't apply to --daemon".to_string());
+        }
+        Invocation::Launch {
+            startup,
+            daemon,
+            dmenu: dmenu.then_some(prompt),
+        }
+    };
+
+    Ok(Cli { config, invocation })
+}
+
+fn parse_command(command: &str, args: &[&str]) -> Result<Invocation, String> {
+    let invocation = match (command, args) {
+        ("toggle" | "show" | "hide" | "state", []) => Invocation::Daemon(command.to_string()),
+        ("clipboard", ["list"]) => Invocation::Clipboard(ClipboardCommand::List),
+        ("clipboard", ["get", n]) => match n.parse::<usize>() {
+            Ok(n) if n > 0 => Invocation::Clipboard(ClipboardCommand::Get(n)),
+            _ => return Err(format!("clipboard get: {:?} is not an entry number", n)),
+        },
+        ("clipboard", ["clear"]) => Invocation::Clipboard(ClipboardCommand::Clear),
+        ("wallpaper", ["next"]) => Invocation::Wallpaper(WallpaperCommand::Next),
+        ("wallpaper", ["previous"]) => Invocation::Wallpaper(WallpaperCommand::Previous),
+        ("wallpaper", ["set", name]) => Invocation::Wallpaper(WallpaperCommand::Set(name.to_string())),
+        ("toggle" | "show" | "hide" | "state" | "clipboard" | "wallpaper", _) => {
+            return Err(format!("wrong arguments for {}", command));
+        }
+        _ => return Err(format!("unknown command {}", command)),
+    };
+    Ok(invocation)
+}
+
+/// Panels and modes are named like in the control socket's JSON
+fn parse_name<T: DeserializeOwned>(flag: &str, name: &str) -> Result<T, String> {
+    serde_json::from_value(serde_json::Value::String(name.to_string()))
+        .map_err(|e| format!("{}: {}", flag, e))
+}
+
+/// Send `request` to the running daemon and return its reply, `Ok(None)` when none is running.
+/// Subcommands then work on the cache files themselves; with a daemon they must not, as it
+/// keeps its own copy of them in memory.
+fn ask_daemon(request: Value) -> Result<Option<Value>, String> {
+    let reply = match ipc::send(&request.to_string()) {
+        Ok(reply) => reply,
+        Err(e) if matches!(e.kind(), io::ErrorKind::NotFound | io::ErrorKind::ConnectionRefused) => {
+            return Ok(None);
+        }
+        Err(e) => return Err(format!("Sierra daemon did not answer: {}", e)),
+    };
+
+    let reply: Value = serde_json::from_str(&reply).unwrap_or_default();
+    if reply["ok"].as_bool() != Some(true) {
+        return Err(reply["error"].as_str().unwrap_or("invalid reply from the daemon").to_string());
+    }
+    Ok(Some(reply))
+}
+
+/// `sierra-launcher clipboard ...`; returns the exit status
+pub fn run_clipboard(command: ClipboardCommand) -> i32 {
+    let request = match command {
+        ClipboardCommand::List | ClipboardCommand::Get(_) => json!({ "command": "clipboard_history" }),
+        ClipboardCommand::Clear => json!({ "command": "clipboard_clear" }),
+    };
+    let reply = match ask_daemon(request) {
+        Ok(reply) => reply,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    };
+
+    if command == ClipboardCommand::Clear {
+        if reply.is_none() {
+            data::init();
+            data::clear();
+        }
+        return 0;
+    }
+
+    let items: Vec<String> = match reply {
+        Some(reply) => serde_json::from_value(reply["result"].clone()).unwrap_or_default(),
+        None => {
+            data::init();
+            data::search_items("").iter().map(|item| item.full_content()).collect()
+        }
+    };
+
+    match command {
+        ClipboardCommand::Get(n) => match items.get(n - 1) {
+            Some(content) => {
+                print!("{}", content);
+                0
+            }
+            None => {
+                eprintln!("No clipboard entry {} ({} in history)", n, items.len());
+                1
+            }
+        },
+        _ => {
+            for (i, content) in items.iter().enumerate() {
+                let line = content.lines().find(|l| !l.trim().is_empty()).unwrap_or_default();
+                let mut preview: String = line.trim().chars().take(PREVIEW_CHARS).collect();
+                if line.trim().chars().count() > PREVIEW_CHARS || content.trim().lines().count() > 1 {
+                    preview.push('…');
+                }
+                println!("{}\t{}", i + 1, preview);
+            }
+            0
+        }
+    }
+}
+
+/// `sierra-launcher wallpaper ...`; returns the exit status
+pub fn run_wallpaper(command: WallpaperCommand) -> i32 {
+    let request = match &command {
+        WallpaperCommand::Next => json!({ "command": "wallpaper", "direction": "next" }),
+        WallpaperCommand::Previous => json!({ "command": "wallpaper", "direction": "previous" }),
+        WallpaperCommand::Set(name) => json!({ "command": "set_wallpaper", "name": name }),
+    };
+    match ask_daemon(request) {
+        Ok(Some(reply)) => {
+            println!("{}", reply["state"]["wallpaper"].as_str().unwrap_or_default());
+            return 0;
+        }
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    }
+
+    let Some(wallpaper_dir) = Config::load().wallpaper_dir else {
+        eprintln!("No wallpaper_dir configured, or it doesn't exist");
+        return 1;
+    };
+    let manager = WallpaperManager::new(wallpaper_dir);
+    manager.ensure_cache();
+    let Some(index) = manager.load_index().filter(|i| !i.wallpapers.is_empty()) else {
+        eprintln!("No wallpapers found");
+        return 1;
+    };
+
+    let wallpapers = &index.wallpapers;
+    let current = manager.current_index(wallpapers);
+    let position = match command {
+        WallpaperCommand::Next => current.map_or(0, |i| (i + 1) % wallpapers.len()),
+        WallpaperCommand::Previous => current.map_or(0, |i| (i + wallpapers.len() - 1) % wallpapers.len()),
+        WallpaperCommand::Set(name) => match index.position(&name) {
+            Some(position) => position,
+            None => {
+                eprintln!("No wallpaper named {:?}", name);
+                return 1;
+            }
+        },
+    };
+
+    manager.set_wallpaper(&wallpapers[position]);
+    println!("{}", wallpapers[position].name);
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_args(args: &[&str]) -> Result<Cli, String> {
+        parse(&args.iter().map(|a| a.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn prompt_needs_dmenu() {
+        assert!(parse_args(&["--prompt", "Pick"]).is_err());
+        assert!(parse_args(&["-p", "Pick", "--daemon"]).is_err());
+
+        let cli = parse_args(&["--dmenu", "-p", "Pick"]).unwrap();
+        assert!(matches!(
+            cli.invocation,
+            Invocation::Launch { dmenu: Some(Some(ref prompt)), .. } if prompt == "Pick"
+        ));
+        let cli = parse_args(&["--prompt=Pick", "--dmenu"]).unwrap();
+        assert!(matches!(cli.invocation, Invocation::Launch { dmenu: Some(Some(_)), .. }));
+    }
+
+    #[test]
+    fn commands_reject_launch_options() {
+        let rejected: [&[&str]; 8] = [
+            &["clipboard", "list", "--panel", "music"],
+            &["--panel=music", "wallpaper", "next"],
+            &["--mode", "files", "toggle"],
+            &["clipboard", "clear", "--clipboard"],
+            &["--daemon", "clipboard", "list"],
+            &["wallpaper", "set", "beach", "--daemon"],
+            &["--dmenu", "show"],
+            &["state", "-p", "Pick"],
+        ];
+        for args in rejected {
+            let error = parse_args(args).unwrap_err();
+            assert!(error.contains("doesn't apply"), "{:?}: {}", args, error);
+        }
+
+        let cli = parse_args(&["-c", "/tmp/Sierra", "wallpaper", "next"]).unwrap();
+        assert!(matches!(cli.invocation, Invocation::Wallpaper(WallpaperCommand::Next)));
+        assert!(matches!(parse_args(&["--daemon", "--help", "toggle"]).unwrap().invocation, Invocation::Help));
+    }
+}
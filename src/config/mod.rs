@@ -1,8 +1,15 @@
 use serde::Deserialize;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::OnceLock;
 use iced::{Font, Color};
 
+use crate::utils::plugins::PluginConfig;
+use crate::utils::quicklinks::{self, Quicklink};
+
+/// Config file given with `--config`, replacing the default location
+static PATH_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct ConfigFile {
     pub font: Option<String>,
@@ -12,6 +19,13 @@ pub struct ConfigFile {
     pub title_text: Option<String>,
     pub title_animation: Option<String>,
     pub wallpaper_dir: Option<String>,
+    pub terminal: Option<String>,
+    pub calculator_skip_history: Option<bool>,
+    pub file_search_hidden: Option<bool>,
+    pub file_search_excludes: Option<Vec<String>>,
+    pub default_quicklinks: Option<bool>,
+    pub quicklinks: Option<Vec<Quicklink>>,
+    pub plugins: Option<Vec<PluginConfig>>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -47,6 +61,18 @@ pub struct Config {
     pub title_text: String,
     pub title_animation: String,
     pub wallpaper_dir: Option<PathBuf>,
+    /// Terminal command prefix for `Terminal=true` apps, e.g. "foot" or "kitty --"
+    pub terminal: Option<String>,
+    /// Keep copied calculator results out of the clipboard history
+    pub calculator_skip_history: bool,
+    /// Index dotfiles and dot-directories for `/` file search
+    pub file_search_hidden: bool,
+    /// .gitignore-style patterns left out of the file index
+    pub file_search_excludes: Vec<String>,
+    /// Keyword-triggered URLs, configured ones first, then the enabled defaults
+    pub quicklinks: Vec<Quicklink>,
+    /// External result providers, each triggered by its prefix
+    pub plugins: Vec<PluginConfig>,
 }
 
 impl Config {
@@ -79,10 +105,28 @@ impl Config {
                 .title_animation
                 .unwrap_or_else(|| "Wave".to_string()),
             wallpaper_dir,
+            terminal: config_file.terminal,
+            calculator_skip_history: config_file.calculator_skip_history.unwrap_or(false),
+            file_search_hidden: config_file.file_search_hidden.unwrap_or(false),
+            file_search_excludes: config_file
+                .file_search_excludes
+                .unwrap_or_else(Self::default_file_search_excludes),
+            quicklinks: quicklinks::merge(
+                config_file.quicklinks.unwrap_or_default(),
+                config_file.default_quicklinks.unwrap_or(true),
+            ),
+            plugins: config_file.plugins.unwrap_or_default(),
         }
     }
 
+    pub fn set_path(path: PathBuf) {
+        let _ = PATH_OVERRIDE.set(path);
+    }
+
     fn config_path() -> PathBuf {
+        if let Some(path) = PATH_OVERRIDE.get() {
+            return path.clone();
+        }
         dirs::config_dir()
             .unwrap_or_else(|| PathBuf::from("."))
             .join("sierra")
@@ -98,6 +142,27 @@ impl Config {
             title_text: Some(" sierra-launcher ".to_string()),
             title_animation: Some("Wave".to_string()),
             wallpaper_dir: Some("~/Pictures/Wallpapers".to_string()),
+            terminal: None,
+            calculator_skip_history: Some(false),
+            file_search_hidden: Some(false),
+            file_search_excludes: None,
+            default_quicklinks: Some(true),
+            quicklinks: None,
+            plugins: None,
+        }
+    }
+
+    fn default_file_search_excludes() -> Vec<String> {
+        crate::utils::file_index::DEFAULT_EXCLUDES
+            .iter()
+            .map(|e| e.to_string())
+            .collect()
+    }
+
+    pub fn file_index_options(&self) -> crate::utils::file_index::FileIndexOptions {
+        crate::utils::file_index::FileIndexOptions {
+            include_hidden: self.file_search_hidden,
+            excludes: self.file_search_excludes.clone(),
         }
     }
 
@@ -162,6 +227,12 @@ impl Default for Config {
             title_text: " sierra-launcher ".to_string(),
             title_animation: "Wave".to_string(),
             wallpaper_dir: None,
+            terminal: None,
+            calculator_skip_history: false,
+            file_search_hidden: false,
+            file_search_excludes: Self::default_file_search_excludes(),
+            quicklinks: Quicklink::defaults(),
+            plugins: Vec::new(),
         }
     }
 }
@@ -1,5 +1,6 @@
 mod utils;
 mod config;
+mod cli;
 mod panels;
 mod app;
 
@@ -7,37 +8,85 @@ use app::state::{Launcher, Panel};
 use app::message::Message;
 
 use iced_layershell::application;
+use iced_layershell::build_pattern::daemon;
 use iced::{Task as Command, Color};
 use iced_layershell::reexport::{Anchor, KeyboardInteractivity};
-use iced_layershell::settings::{LayerShellSettings, Settings};
+use iced_layershell::settings::{LayerShellSettings, Settings, StartMode};
 
 use crate::utils::theme::Theme;
 use crate::utils::watcher::ColorWatcher;
 use crate::utils::wallpaper_manager::{WallpaperManager, WallpaperIndex};
 use crate::config::Config;
+use crate::utils::dmenu;
+use crate::utils::ipc;
 
-use crate::panels::search_bar::SearchBar;
+use crate::panels::search_bar::{self, SearchBar};
 use crate::panels::app_list::AppList;
 use crate::panels::mpris_player::MusicPlayer;
 use crate::panels::system::SystemPanel;
 use crate::panels::services::ServicesPanel;
 use crate::panels::weather::WeatherPanel;
 use crate::panels::title_color::TitleAnimator;
+use crate::panels::shell::ShellPanel;
+use crate::panels::calculator::CalculatorPanel;
+use crate::panels::file_search::FileSearchPanel;
+use crate::panels::recent::RecentPanel;
+use crate::panels::symbols::SymbolPanel;
+use crate::panels::windows::WindowPanel;
+use app::state::SearchMode;
 
 use std::time::Instant;
 
 fn main() -> Result<(), iced_layershell::Error> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let cli = match cli::parse(&args) {
+        Ok(cli) => cli,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, cli::USAGE);
+            std::process::exit(cli::EXIT_USAGE);
+        }
+    };
+    if let Some(path) = cli.config {
+        Config::set_path(path);
+    }
+
+    let (startup, daemon, dmenu) = match cli.invocation {
+        cli::Invocation::Launch { startup, daemon, dmenu } => (startup, daemon, dmenu),
+        // `sierra-launcher toggle` only pokes a running daemon; keep it as quick as possible
+        cli::Invocation::Daemon(request) => std::process::exit(send_to_daemon(&request)),
+        cli::Invocation::Clipboard(command) => std::process::exit(cli::run_clipboard(command)),
+        cli::Invocation::Wallpaper(command) => std::process::exit(cli::run_wallpaper(command)),
+        cli::Invocation::Version => {
+            println!("sierra-launcher {}", env!("CARGO_PKG_VERSION"));
+            return Ok(());
+        }
+        cli::Invocation::Help => {
+            print!("{}", cli::USAGE);
+            return Ok(());
+        }
+    };
+
     eprintln!("[Main] ========== STARTUP ==========");
     let app_start = Instant::now();
     eprintln!("[Main] Starting at: {:?}", app_start);
 
+    // Entries have to be read before the window grabs the terminal's attention
+    if let Some(prompt) = dmenu {
+        dmenu::init(prompt);
+    }
+
+    if daemon {
+        return run_daemon();
+    }
+    cli::set_startup(startup);
+
     application(new, namespace, update, view)
         .settings(Settings {
             layer_settings: LayerShellSettings {
-                size: Some((484, 714)),
+                size: Some(app::daemon::SIZE),
                 anchor: Anchor::Bottom,
                 keyboard_interactivity: KeyboardInteractivity::Exclusive,
-                margin: (0, 0, 4, 0),
+                margin: app::daemon::MARGIN,
                 ..Default::default()
             },
             ..Default::default()
@@ -46,12 +95,60 @@ fn main() -> Result<(), iced_layershell::Error> {
             background_color: Color::TRANSPARENT,
             text_color: Color::WHITE,
         })
-        .subscription(|_| app::subscription::subscription())
+        .subscription(app::subscription::subscription)
         .run()?;
 
     Ok(())
 }
 
+/// Resident launcher: starts without a surface and maps one on `show`/`toggle`
+fn run_daemon() -> Result<(), iced_layershell::Error> {
+    if let Err(e) = ipc::bind() {
+        eprintln!("[Main] Can't start daemon: {}", e);
+        std::process::exit(1);
+    }
+
+    // Child watches gio adds for launched apps only reap them while the default main context runs
+    std::thread::spawn(|| gio::glib::MainLoop::new(None, false).run());
+
+    daemon(new_daemon, namespace, update_daemon, view_surface)
+        .settings(Settings {
+            layer_settings: LayerShellSettings {
+                start_mode: StartMode::Background,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .style(|_theme, _id| iced::theme::Style {
+            background_color: Color::TRANSPARENT,
+            text_color: Color::WHITE,
+        })
+        .subscription(app::subscription::subscription)
+        .run()
+}
+
+/// Client side of `sierra-launcher toggle|show|hide|state`; returns the exit status.
+/// `state` prints the daemon's JSON state for status bars.
+fn send_to_daemon(request: &str) -> i32 {
+    let reply = match ipc::send(request) {
+        Ok(reply) => reply,
+        Err(e) => {
+            eprintln!("No Sierra daemon running on {} ({})", ipc::socket_path().display(), e);
+            return 1;
+        }
+    };
+
+    let reply: serde_json::Value = serde_json::from_str(&reply).unwrap_or_default();
+    if reply["ok"].as_bool() != Some(true) {
+        eprintln!("{}", reply["error"].as_str().unwrap_or("invalid reply from the daemon"));
+        return 1;
+    }
+    if request == "state" {
+        println!("{}", reply["state"]);
+    }
+    0
+}
+
 fn new() -> (Launcher, Command<Message>) {
     let start = Instant::now();
     eprintln!("[Main] Initializing launcher...");
@@ -59,27 +156,42 @@ fn new() -> (Launcher, Command<Message>) {
     crate::utils::data::init();
     eprintln!("[Main] Clipboard init: {:?}", start.elapsed());
 
+    crate::utils::frecency::init();
+
     let config = Config::load();
     eprintln!("[Main] Config load: {:?}", start.elapsed());
 
-    let wallpaper_index: Option<WallpaperIndex> =
+    let (wallpaper_index, wallpaper_selected_index): (Option<WallpaperIndex>, usize) =
         if let Some(wallpaper_dir) = config.wallpaper_dir.clone() {
             let manager = WallpaperManager::new(wallpaper_dir);
             manager.ensure_cache();
-            manager.load_index()
+            let index = manager.load_index();
+            let selected = index.as_ref().and_then(|i| manager.current_index(&i.wallpapers));
+            (index, selected.unwrap_or(0))
         } else {
-            None
+            (None, 0)
         };
 
     eprintln!("[Main] Wallpaper cache ready: {:?}", start.elapsed());
 
     let theme = Theme::load_from_config(&config);
 
-    let _clipboard_monitor = crate::utils::monitor::start_monitor();
+    // A script picking a line shouldn't record clipboard history
+    let _clipboard_monitor = (!dmenu::is_active()).then(crate::utils::monitor::start_monitor);
     let watcher = ColorWatcher::new().ok();
 
-    let search_bar = SearchBar::new();
-    let app_list = AppList::new();
+    let mut search_bar = SearchBar::new();
+    let mut app_list = AppList::new()
+        .with_terminal(config.terminal.clone())
+        .with_quicklinks(config.quicklinks.clone())
+        .with_plugins(config.plugins.clone());
+    if let Some(options) = dmenu::options() {
+        app_list = app_list.with_dmenu(options.lines.clone());
+        if let Some(prompt) = &options.prompt {
+            search_bar = search_bar.with_placeholder(prompt.clone());
+        }
+    }
+    let file_search = FileSearchPanel::new(config.file_index_options());
     let weather_panel = WeatherPanel::new();
     let music_player = MusicPlayer::new();
     let system_panel = SystemPanel::new();
@@ -89,6 +201,17 @@ fn new() -> (Launcher, Command<Message>) {
         .with_mode(config.get_animation_mode())
         .with_speed(80);
 
+    // `--panel` and `--mode` go through the same messages as picking them by hand
+    let startup = cli::startup();
+    let mut boot = Vec::new();
+    if let Some(panel) = startup.panel {
+        boot.push(Command::done(Message::SetPanel(panel)));
+    }
+    if let Some(prefix) = startup.mode.and_then(SearchMode::prefix) {
+        let input = search_bar::Message::InputChanged(prefix.to_string());
+        boot.push(Command::done(Message::SearchBarMessage(input)));
+    }
+
     (
         Launcher {
             theme,
@@ -96,6 +219,14 @@ fn new() -> (Launcher, Command<Message>) {
             config,
             search_bar,
             app_list,
+            search_mode: SearchMode::Apps,
+            shell_panel: ShellPanel::new(),
+            calculator_panel: CalculatorPanel::new(),
+            file_search,
+            recent_panel: RecentPanel::new(),
+            symbol_panel: SymbolPanel::new(),
+            window_panel: WindowPanel::new(),
+            modifiers: iced::keyboard::Modifiers::default(),
             current_panel: Panel::Clock,
             weather_panel,
             music_player,
@@ -106,18 +237,29 @@ fn new() -> (Launcher, Command<Message>) {
             frame_count: 0,
             title_animator,
             control_center_visible: false,
-            clipboard_visible: false,
+            clipboard_visible: startup.clipboard,
             clipboard_selected_index: 0,
             is_first_frame: true,
 
             // ✅ NEW — wallpaper data now lives in state
             wallpaper_index,
-            wallpaper_selected_index: 0,
+            wallpaper_selected_index,
+            daemon: false,
+            surface: None,
         },
-        Command::none(),
+        Command::batch(boot),
     )
 }
 
+fn new_daemon() -> (Launcher, Command<Message>) {
+    let (mut launcher, task) = new();
+    launcher.daemon = true;
+    // Nothing is drawn until the first show, so don't wait for a frame to start loading
+    launcher.app_list.start_loading();
+    app::dbus::publish(&launcher);
+    (launcher, task)
+}
+
 fn namespace() -> String {
     String::from("iced_launcher2")
 }
@@ -126,6 +268,18 @@ fn update(launcher: &mut Launcher, message: Message) -> Command<Message> {
     app::update::update(launcher, message)
 }
 
+/// `update` for `--daemon`, which also keeps the D-Bus properties current
+fn update_daemon(launcher: &mut Launcher, message: Message) -> Command<Message> {
+    let task = app::update::update(launcher, message);
+    app::dbus::publish(launcher);
+    task
+}
+
 fn view(launcher: &Launcher) -> iced::Element<'_, Message> {
     app::view::view(launcher)
 }
+
+/// A daemon has at most one surface, so every window shows the launcher
+fn view_surface(launcher: &Launcher, _id: iced::window::Id) -> iced::Element<'_, Message> {
+    app::view::view(launcher)
+}
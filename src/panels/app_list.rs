@@ -1,11 +1,32 @@
-use iced::widget::{column, container, row, scrollable, text};
+use iced::widget::{column, container, image, row, scrollable, text, Space};
 use iced::{Border, Element, Length, Task};
 use gio::prelude::*;
 use gio::{AppLaunchContext, DesktopAppInfo};
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use arc_swap::ArcSwapOption;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, OnceLock};
 use std::thread;
 
+use crate::panels::list_window::{self, ListWindow};
+use crate::utils::convert::{self, Conversion};
+use crate::utils::copy;
+use crate::utils::frecency;
+use crate::utils::icons::IconLoader;
+use crate::utils::normalize;
+use crate::utils::recent::{self, RecentDocument};
+use crate::utils::path_index::{self, Executable};
+use crate::utils::quicklinks::{self, Quicklink};
+use crate::utils::ssh::{self, SshHost};
+use crate::utils::plugins::{self, Plugin, PluginConfig, PluginItem, PluginStatus};
+use crate::utils::terminal;
 use crate::utils::theme::Theme;
+use crate::utils::watcher::AppDirWatcher;
 
 #[derive(Debug, Clone)]
 pub enum Message {
@@ -13,21 +34,169 @@ pub enum Message {
     ArrowUp,
     ArrowDown,
     LaunchSelected,
+    ToggleActions,
+    /// A plugin is done with the item it was handed
+    PluginActivated { plugin: String, item: String, handled: bool },
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct App {
     id: String,
     name: String,
+    /// Localized name folded for matching (lowercase, no diacritics), char-aligned with `name`
     name_lower: String,
+    /// Untranslated `Name`, folded; only set when it differs from the localized one
+    untranslated_name_lower: Option<String>,
     description_lower: Option<String>,
+    /// Secondary search fields, all lowercased; weighted below the name
+    generic_name_lower: Option<String>,
+    keywords_lower: Option<String>,
+    exec_lower: Option<String>,
+    categories_lower: Vec<String>,
+    /// Themed icon name or absolute path
+    icon: Option<String>,
+    /// Backing .desktop file and its mtime, used to skip unchanged entries on refresh
+    path: Option<PathBuf>,
+    mtime: u64,
+}
+
+const INDEX_CACHE_FILE: &str = ".cache/sierra/apps.cache";
+
+/// On-disk copy of the app index for instant first paint
+#[derive(Debug, Serialize, Deserialize)]
+struct AppIndexCache {
+    /// LC_MESSAGES locale the localized names were read in
+    locale: String,
+    /// mtime of each applications directory when the index was built
+    dir_mtimes: Vec<(PathBuf, u64)>,
+    apps: Vec<App>,
+}
+
+fn index_cache_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(INDEX_CACHE_FILE)
+}
+
+fn mtime_secs(path: &Path) -> u64 {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Adding or removing a .desktop file bumps its directory's mtime. Subdirectories
+/// (`applications/wine/...`) count too, since that doesn't reach the parent.
+fn current_dir_mtimes() -> Vec<(PathBuf, u64)> {
+    let mut mtimes = Vec::new();
+    for dir in AppDirWatcher::application_dirs() {
+        push_dir_mtimes(dir, &mut mtimes);
+    }
+    mtimes
 }
 
-static APP_CACHE: OnceLock<Vec<App>> = OnceLock::new();
+fn push_dir_mtimes(dir: PathBuf, mtimes: &mut Vec<(PathBuf, u64)>) {
+    let mtime = mtime_secs(&dir);
+    let mut subdirs: Vec<PathBuf> = fs::read_dir(&dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        // Symlinked directories aren't followed, so a link loop can't recurse forever
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .map(|entry| entry.path())
+        .collect();
+    subdirs.sort();
+
+    mtimes.push((dir, mtime));
+    for subdir in subdirs {
+        push_dir_mtimes(subdir, mtimes);
+    }
+}
+
+fn load_index_cache() -> Option<AppIndexCache> {
+    let content = fs::read(index_cache_path()).ok()?;
+    let cache = bincode::deserialize::<AppIndexCache>(&content).ok()?;
+
+    // Names were localized for another locale; none of the entries can be reused
+    if cache.locale != normalize::messages_locale() {
+        eprintln!("[AppList] App index cache built for locale {:?} - ignoring", cache.locale);
+        return None;
+    }
+    Some(cache)
+}
+
+fn save_index_cache(apps: &[App]) {
+    let path = index_cache_path();
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let cache = AppIndexCache {
+        locale: normalize::messages_locale(),
+        dir_mtimes: current_dir_mtimes(),
+        apps: apps.to_vec(),
+    };
+    if let Ok(encoded) = bincode::serialize(&cache) {
+        if let Err(e) = fs::write(&path, encoded) {
+            eprintln!("[AppList] Failed to save app index cache: {}", e);
+        }
+    }
+}
+
+/// Current app index, swapped atomically when the applications directories change
+static APP_CACHE: ArcSwapOption<Vec<App>> = ArcSwapOption::const_empty();
+
+/// Bumped every time APP_CACHE is replaced
+static APP_GENERATION: AtomicU64 = AtomicU64::new(0);
 
 // Track loading state
 static LOADING_STATE: OnceLock<Arc<Mutex<LoadingState>>> = OnceLock::new();
 
+/// Cap on PATH executables shown below the desktop apps
+const MAX_EXECUTABLE_RESULTS: usize = 20;
+/// Cap on recent documents shown at the bottom of the list
+const MAX_RECENT_RESULTS: usize = 5;
+/// First word of a query that lists SSH hosts, e.g. `ssh web`
+const SSH_KEYWORD: &str = "ssh";
+
+/// What a result row launches
+#[derive(Debug, Clone)]
+enum ResultKind {
+    App(usize),
+    /// Index into the PATH executables, with the arguments typed after its name
+    Executable(usize, String),
+    /// Unit or time zone conversion of the whole query; Enter copies its value
+    Conversion(Conversion),
+    /// Index into the recent documents
+    Recent(usize),
+    /// Index into the quicklinks, with the query typed after its keyword
+    Quicklink(usize, String),
+    /// Index into the SSH hosts
+    Ssh(usize),
+    /// Index into the plugins, and the item it returned
+    Plugin(usize, PluginItem),
+    /// Index into the `--dmenu` lines
+    Line(usize),
+}
+
+#[derive(Debug, Clone)]
+struct ResultRow {
+    kind: ResultKind,
+    /// Matched character positions in the row title
+    positions: Vec<usize>,
+}
+
+/// Desktop Entry actions of the selected app, shown as sub-rows
+#[derive(Debug, Clone)]
+struct ExpandedActions {
+    /// Position in `results` of the app that owns the actions
+    list_index: usize,
+    /// (action id, display name)
+    actions: Vec<(String, String)>,
+    /// Highlighted action; `None` keeps the app row itself selected
+    selected: Option<usize>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 enum LoadingState {
     NotStarted,
@@ -36,36 +205,128 @@ enum LoadingState {
 }
 
 pub struct AppList {
-    filtered_indices: Vec<usize>,
+    /// Snapshot of APP_CACHE that `results` points into
+    apps: Arc<Vec<App>>,
+    generation: u64,
+    /// Snapshot of the PATH index, `None` until it is ready
+    executables: Option<Arc<Vec<Executable>>>,
+    /// Snapshot of the recent documents, `None` until they are loaded
+    recent: Option<Arc<Vec<RecentDocument>>>,
+    /// Snapshot of the SSH hosts, `None` until they are loaded
+    ssh_hosts: Option<Arc<Vec<SshHost>>>,
+    results: Vec<ResultRow>,
+    matcher: SkimMatcherV2,
     pub search_query: String,
-    pub selected_index: usize,
+    list: ListWindow,
     scroll_id: iced::widget::Id,
-    window_size: usize,
-    window_start: usize,
     is_loading: bool,
+    expanded: Option<ExpandedActions>,
+    icons: IconLoader,
+    /// Configured terminal for `Terminal=true` apps; auto-detected when unset
+    terminal: Option<String>,
+    quicklinks: Vec<Quicklink>,
+    plugins: Vec<Plugin>,
+    /// Plugin that owns the current query, the query last sent to it, and the
+    /// generation of its answers shown in `results`
+    active_plugin: Option<(usize, String, u64)>,
+    /// `--dmenu` entries with their folded form; when set they replace everything else
+    dmenu: Option<Vec<(String, String)>>,
 }
 
 impl AppList {
-    /// Create new AppList - DOES NOT load apps yet!
-    /// Apps will be loaded asynchronously after window appears
+    /// Create new AppList from the on-disk index if it is still valid.
+    /// Enumeration happens asynchronously after window appears
     pub fn new() -> Self {
         // Initialize loading state tracker
         LOADING_STATE.get_or_init(|| Arc::new(Mutex::new(LoadingState::NotStarted)));
+
+        if APP_CACHE.load().is_none() {
+            match load_index_cache() {
+                Some(cache) if cache.dir_mtimes == current_dir_mtimes() => {
+                    eprintln!("[AppList] Loaded {} apps from index cache", cache.apps.len());
+                    Self::store_apps(cache.apps);
+                }
+                Some(_) => eprintln!("[AppList] App index cache outdated"),
+                None => {}
+            }
+        }
         
-        Self {
-            filtered_indices: Vec::new(), // Start empty
+        let mut app_list = Self {
+            apps: Arc::new(Vec::new()),
+            generation: 0,
+            executables: None,
+            recent: None,
+            ssh_hosts: None,
+            results: Vec::new(), // Start empty
+            matcher: SkimMatcherV2::default(),
             search_query: String::new(),
-            selected_index: 0,
+            list: ListWindow::new(),
             scroll_id: iced::widget::Id::unique(),
-            window_size: 17,
-            window_start: 0,
             is_loading: false, // Not loading yet - wait for start_loading() call
+            expanded: None,
+            icons: IconLoader::new(),
+            terminal: None,
+            quicklinks: Vec::new(),
+            plugins: Vec::new(),
+            active_plugin: None,
+            dmenu: None,
+        };
+
+        if APP_CACHE.load().is_some() {
+            app_list.refresh_snapshot();
+        }
+
+        app_list
+    }
+
+    pub fn with_terminal(mut self, terminal: Option<String>) -> Self {
+        self.terminal = terminal;
+        self
+    }
+
+    pub fn with_quicklinks(mut self, quicklinks: Vec<Quicklink>) -> Self {
+        self.quicklinks = quicklinks;
+        self
+    }
+
+    pub fn with_plugins(mut self, plugins: Vec<PluginConfig>) -> Self {
+        self.plugins = plugins.into_iter().map(Plugin::new).collect();
+        self
+    }
+
+    pub fn with_dmenu(mut self, lines: Vec<String>) -> Self {
+        let lines: Vec<(String, String)> = lines
+            .into_iter()
+            .map(|line| {
+                let folded = normalize::fold(&line);
+                (line, folded)
+            })
+            .collect();
+        self.dmenu = Some(lines);
+        self.filter_apps();
+        self
+    }
+
+    pub fn is_dmenu(&self) -> bool {
+        self.dmenu.is_some()
+    }
+
+    /// The highlighted `--dmenu` line
+    pub fn selected_line(&self) -> Option<&str> {
+        match self.results.get(self.list.selected())?.kind {
+            ResultKind::Line(idx) => self.dmenu.as_ref().map(|lines| lines[idx].0.as_str()),
+            _ => None,
         }
     }
 
     /// Trigger lazy loading of apps in background thread
     /// Call this AFTER the first frame is rendered
     pub fn start_loading(&mut self) {
+        // Picking from stdin never needs the app index
+        if self.dmenu.is_some() {
+            return;
+        }
+
         let state = LOADING_STATE.get().unwrap().clone();
         let mut state_lock = state.lock().unwrap();
         
@@ -74,70 +335,160 @@ impl AppList {
             drop(state_lock);
             
             // If already loaded, populate immediately
-            if APP_CACHE.get().is_some() {
-                let total_apps = Self::all_apps().len();
-                self.filtered_indices = (0..total_apps).collect();
+            if APP_CACHE.load().is_some() {
                 self.is_loading = false;
-                eprintln!("[AppList] Apps already cached - {} total", total_apps);
+                self.refresh_snapshot();
+                eprintln!("[AppList] Apps already cached - {} total", self.apps.len());
             }
             return;
         }
         
         *state_lock = LoadingState::Loading;
         drop(state_lock);
+
+        path_index::start();
+        recent::start();
+        ssh::start();
         
-        self.is_loading = true;
+        // With a valid index cache the list is already usable; refresh it quietly
+        self.is_loading = APP_CACHE.load().is_none();
         eprintln!("[AppList] Starting lazy app loading...");
         
         // Load apps in background thread
         thread::spawn(move || {
             let start = std::time::Instant::now();
-            
-            // This will initialize APP_CACHE
-            APP_CACHE.get_or_init(|| Self::load_desktop_apps());
+
+            let previous = APP_CACHE
+                .load_full()
+                .map(|apps| apps.to_vec())
+                .or_else(|| load_index_cache().map(|cache| cache.apps))
+                .unwrap_or_default();
+            Self::refresh_apps(&previous);
             
             // Update state
             let state = LOADING_STATE.get().unwrap();
             *state.lock().unwrap() = LoadingState::Loaded;
             
-            eprintln!("[AppList] ✓ Loaded {} apps in {:?}", 
-                APP_CACHE.get().unwrap().len(), 
-                start.elapsed()
-            );
+            eprintln!("[AppList] ✓ Loaded apps in {:?}", start.elapsed());
+
+            Self::watch_app_dirs();
         });
     }
 
-    /// Check if apps are loaded and update filtered list
-    /// Returns true if apps just finished loading
-    pub fn check_loaded(&mut self) -> bool {
-        if !self.is_loading {
-            return false;
+    /// Rebuild the index whenever a .desktop file is added, removed or edited.
+    /// Runs on the loader thread for the lifetime of the process.
+    fn watch_app_dirs() {
+        let mut watcher = match AppDirWatcher::new() {
+            Ok(w) => w,
+            Err(e) => {
+                eprintln!("[AppList] Not watching applications directories: {}", e);
+                return;
+            }
+        };
+
+        while watcher.wait_for_change() {
+            eprintln!("[AppList] Applications changed - rebuilding index");
+            let previous = APP_CACHE.load_full().unwrap_or_default();
+            Self::refresh_apps(&previous);
         }
-        
-        let state = LOADING_STATE.get().unwrap();
-        let state_lock = state.lock().unwrap();
-        
-        if *state_lock == LoadingState::Loaded {
-            drop(state_lock);
-            
-            // Apps are now available - populate the list
-            let total_apps = Self::all_apps().len();
-            self.filtered_indices = (0..total_apps).collect();
+    }
+
+    /// Rebuild the index on top of `previous`, publishing and persisting it only if it changed
+    fn refresh_apps(previous: &[App]) {
+        let apps = Self::load_desktop_apps(previous);
+        let unchanged = APP_CACHE
+            .load()
+            .as_deref()
+            .is_some_and(|current| *current == apps);
+
+        if !unchanged {
+            save_index_cache(&apps);
+            Self::store_apps(apps);
+        }
+    }
+
+    fn store_apps(apps: Vec<App>) {
+        APP_CACHE.store(Some(Arc::new(apps)));
+        APP_GENERATION.fetch_add(1, Ordering::Release);
+    }
+
+    /// Reload PATH executables, recent documents and SSH hosts in the background, e.g. when the
+    /// daemon shows the launcher. `check_loaded` picks them up.
+    pub fn refresh_sources() {
+        path_index::refresh();
+        recent::refresh();
+        ssh::refresh();
+    }
+
+    fn is_snapshot<T>(current: &Option<Arc<T>>, loaded: &Arc<T>) -> bool {
+        current.as_ref().is_some_and(|current| Arc::ptr_eq(current, loaded))
+    }
+
+    /// Check if apps are loaded (or were reloaded) and update filtered list
+    /// Returns true if the list changed
+    pub fn check_loaded(&mut self) -> bool {
+        if self.is_loading {
+            let state = LOADING_STATE.get().unwrap();
+            if *state.lock().unwrap() != LoadingState::Loaded {
+                return false;
+            }
+
+            // Apps are now available - populate the list (respecting any query typed meanwhile)
             self.is_loading = false;
-            
-            eprintln!("[AppList] ✓ Apps populated - {} total", total_apps);
+            self.refresh_snapshot();
+
+            eprintln!("[AppList] ✓ Apps populated - {} total", self.apps.len());
+            return true;
+        }
+
+        if APP_GENERATION.load(Ordering::Acquire) != self.generation {
+            self.refresh_snapshot();
+            eprintln!("[AppList] ✓ App index reloaded - {} total", self.apps.len());
+            return true;
+        }
+
+        // Snapshots are replaced whenever a source is reloaded, see `refresh_sources`
+        let executables = path_index::executables().filter(|new| !Self::is_snapshot(&self.executables, new));
+        let recent = recent::documents().filter(|new| !Self::is_snapshot(&self.recent, new));
+        let ssh_hosts = ssh::hosts().filter(|new| !Self::is_snapshot(&self.ssh_hosts, new));
+        if executables.is_some() || recent.is_some() || ssh_hosts.is_some() {
+            self.executables = executables.or(self.executables.take());
+            self.recent = recent.or(self.recent.take());
+            self.ssh_hosts = ssh_hosts.or(self.ssh_hosts.take());
+            if !self.search_query.is_empty() {
+                self.filter_apps();
+            }
             return true;
         }
         
         false
     }
 
-    fn all_apps() -> &'static [App] {
-        APP_CACHE.get().map_or(&[], |v| v.as_slice())
+    /// Pick up new answers from the plugin that owns the query; true if the list changed
+    pub fn check_plugins(&mut self) -> bool {
+        let Some((idx, _, generation)) = &self.active_plugin else {
+            return false;
+        };
+        if self.plugins[*idx].generation() == *generation {
+            return false;
+        }
+        self.filter_apps();
+        true
+    }
+
+    /// Take the latest index and re-run the current query against it
+    fn refresh_snapshot(&mut self) {
+        self.generation = APP_GENERATION.load(Ordering::Acquire);
+        self.apps = APP_CACHE.load_full().unwrap_or_default();
+        self.filter_apps();
     }
 
-    fn load_desktop_apps() -> Vec<App> {
+    /// Enumerate desktop apps, reusing entries of `previous` whose .desktop file is unchanged
+    fn load_desktop_apps(previous: &[App]) -> Vec<App> {
         eprintln!("[AppList] Loading desktop applications...");
+
+        let previous: HashMap<&str, &App> =
+            previous.iter().map(|app| (app.id.as_str(), app)).collect();
         
         let mut apps: Vec<App> = gio::AppInfo::all()
             .into_iter()
@@ -148,15 +499,59 @@ impl AppList {
                     return None;
                 }
 
+                let id = desktop.id()?.to_string();
+                let path = desktop.filename();
+                let mtime = path.as_deref().map_or(0, mtime_secs);
+
+                if let Some(&cached) = previous.get(id.as_str()) {
+                    if cached.path == path && cached.mtime == mtime {
+                        return Some(cached.clone());
+                    }
+                }
+
                 let name = desktop.name().to_string();
-                let name_lower = name.to_lowercase();
-                let description_lower = desktop.description().map(|d| d.to_lowercase());
+                let name_lower = normalize::fold(&name);
+                let untranslated_name_lower = desktop
+                    .string("Name")
+                    .map(|n| normalize::fold(&n))
+                    .filter(|n| *n != name_lower);
+                let description_lower = desktop.description().map(|d| normalize::fold(&d));
+                let generic_name_lower = desktop.generic_name().map(|g| normalize::fold(&g));
+                let keywords = desktop.keywords();
+                let keywords_lower = (!keywords.is_empty()).then(|| {
+                    keywords.iter().map(|k| normalize::fold(k)).collect::<Vec<_>>().join(" ")
+                });
+                let exec_lower = desktop
+                    .executable()
+                    .file_name()
+                    .map(|e| normalize::fold(&e.to_string_lossy()));
+                let categories_lower = desktop
+                    .categories()
+                    .map(|c| {
+                        c.split(';')
+                            .filter(|c| !c.is_empty())
+                            .map(normalize::fold)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let icon = desktop
+                    .icon()
+                    .and_then(|icon| IconExt::to_string(&icon))
+                    .map(|icon| icon.to_string());
 
                 Some(App {
-                    id: desktop.id()?.to_string(),
+                    id,
                     name,
                     name_lower,
+                    untranslated_name_lower,
                     description_lower,
+                    generic_name_lower,
+                    keywords_lower,
+                    exec_lower,
+                    categories_lower,
+                    icon,
+                    path,
+                    mtime,
                 })
             })
             .collect();
@@ -169,51 +564,327 @@ impl AppList {
     }
 
     fn filter_apps(&mut self) {
-        let apps = Self::all_apps();
+        let apps = Arc::clone(&self.apps);
         
+        self.results.clear();
+        self.expanded = None;
+
+        if self.push_dmenu_results() || self.push_plugin_results() {
+            self.list.clamp(self.results.len());
+            return;
+        }
+
+        if let Some((idx, query)) = quicklinks::find(&self.quicklinks, &self.search_query) {
+            self.results.push(ResultRow {
+                kind: ResultKind::Quicklink(idx, query),
+                positions: Vec::new(),
+            });
+        }
+
+        if let Some(conversion) = convert::convert(&self.search_query) {
+            self.results.push(ResultRow {
+                kind: ResultKind::Conversion(conversion),
+                positions: Vec::new(),
+            });
+        }
+
+        self.push_ssh_results();
+
         if apps.is_empty() {
-            self.filtered_indices.clear();
             return;
         }
         
-        if self.search_query.is_empty() {
-            self.filtered_indices = (0..apps.len()).collect();
+        let (category, query) = Self::parse_query(&self.search_query);
+        let candidates = apps.iter().enumerate().filter(|(_, app)| {
+            category.as_deref().is_none_or(|c| app.categories_lower.iter().any(|ac| ac.starts_with(c)))
+        });
+
+        if query.is_empty() {
+            // Most used apps first, the rest stay alphabetical
+            let mut ranked: Vec<(usize, f64)> = candidates
+                .map(|(idx, app)| (idx, frecency::score(&app.id)))
+                .collect();
+            ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+            self.results.extend(ranked.into_iter().map(|(idx, _)| ResultRow {
+                kind: ResultKind::App(idx),
+                positions: Vec::new(),
+            }));
         } else {
-            let q = self.search_query.to_lowercase();
-
-            self.filtered_indices.clear();
-            self.filtered_indices.extend(
-                apps.iter()
-                    .enumerate()
-                    .filter(|(_, app)| {
-                        app.name_lower.contains(&q)
-                            || app
-                                .description_lower
-                                .as_deref()
-                                .map_or(false, |d| d.contains(&q))
-                    })
-                    .map(|(idx, _)| idx),
-            );
+            let query_lower = normalize::fold(&query);
+            let matcher = &self.matcher;
+
+            // Name matches carry highlight positions; other fields rank below them
+            let mut scored: Vec<(usize, i64, Vec<usize>)> = candidates
+                .filter_map(|(idx, app)| {
+                    let bonus = frecency::bonus(&app.id);
+                    if let Some((score, positions)) = matcher.fuzzy_indices(&app.name_lower, &query_lower) {
+                        return Some((idx, score * 2 + bonus, positions));
+                    }
+                    if let Some(score) = app
+                        .untranslated_name_lower
+                        .as_deref()
+                        .and_then(|n| matcher.fuzzy_match(n, &query_lower))
+                    {
+                        return Some((idx, score * 3 / 2 + bonus, Vec::new()));
+                    }
+                    Self::secondary_score(matcher, app, &query_lower)
+                        .map(|score| (idx, score + bonus, Vec::new()))
+                })
+                .collect();
+
+            // Stable sort keeps alphabetical order for equal scores
+            scored.sort_by_key(|s| std::cmp::Reverse(s.1));
+
+            for (idx, _, positions) in scored {
+                self.results.push(ResultRow {
+                    kind: ResultKind::App(idx),
+                    positions,
+                });
+            }
+
+            if category.is_none() {
+                self.push_executable_results(&query);
+                self.push_recent_results(&query_lower);
+            }
+        }
+
+        self.list.clamp(self.results.len());
+    }
+
+    /// PATH executables matching the first word of the query, ranked below the desktop apps.
+    /// The rest of the query becomes the command's arguments.
+    fn push_executable_results(&mut self, query: &str) {
+        let Some(executables) = self.executables.clone() else {
+            return;
+        };
+        let (command, args) = match query.split_once(char::is_whitespace) {
+            Some((command, args)) => (command, args.trim()),
+            None => (query, ""),
+        };
+
+        // Binaries already reachable through a matched desktop entry
+        let covered: Vec<&str> = self
+            .results
+            .iter()
+            .filter_map(|row| match row.kind {
+                ResultKind::App(idx) => self.apps[idx].exec_lower.as_deref(),
+                _ => None,
+            })
+            .collect();
+
+        let mut scored: Vec<(usize, i64, Vec<usize>)> = executables
+            .iter()
+            .enumerate()
+            .filter(|(_, exe)| !covered.contains(&exe.name.as_str()))
+            .filter_map(|(idx, exe)| {
+                let (score, positions) = self.matcher.fuzzy_indices(&exe.name, command)?;
+                let bonus = frecency::bonus(&Self::executable_id(&exe.name));
+                Some((idx, score + bonus, positions))
+            })
+            .collect();
+
+        scored.sort_by_key(|s| std::cmp::Reverse(s.1));
+
+        self.results.extend(scored.into_iter().take(MAX_EXECUTABLE_RESULTS).map(
+            |(idx, _, positions)| ResultRow {
+                kind: ResultKind::Executable(idx, args.to_string()),
+                positions,
+            },
+        ));
+    }
+
+    /// Recent documents matching the query, below everything else
+    fn push_recent_results(&mut self, query_lower: &str) {
+        let Some(documents) = self.recent.clone() else {
+            return;
+        };
+
+        let mut scored: Vec<(usize, i64, Vec<usize>)> = documents
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, doc)| {
+                let (score, positions) = self.matcher.fuzzy_indices(&doc.name_lower, query_lower)?;
+                Some((idx, score, positions))
+            })
+            .collect();
+
+        scored.sort_by_key(|s| std::cmp::Reverse(s.1));
+
+        self.results.extend(scored.into_iter().take(MAX_RECENT_RESULTS).map(
+            |(idx, _, positions)| ResultRow {
+                kind: ResultKind::Recent(idx),
+                positions,
+            },
+        ));
+    }
+
+    /// `--dmenu` lines matching the query, in input order while the query is empty.
+    /// Returns false outside dmenu mode.
+    fn push_dmenu_results(&mut self) -> bool {
+        let Some(lines) = &self.dmenu else {
+            return false;
+        };
+
+        let query = normalize::fold(self.search_query.trim());
+        if query.is_empty() {
+            self.results.extend((0..lines.len()).map(|idx| ResultRow {
+                kind: ResultKind::Line(idx),
+                positions: Vec::new(),
+            }));
+            return true;
         }
 
-        if self.selected_index >= self.filtered_indices.len() {
-            self.selected_index = 0;
+        let mut scored: Vec<(usize, i64, Vec<usize>)> = lines
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, (_, folded))| {
+                let (score, positions) = self.matcher.fuzzy_indices(folded, &query)?;
+                Some((idx, score, positions))
+            })
+            .collect();
+
+        // Stable sort keeps input order for equal scores
+        scored.sort_by_key(|s| std::cmp::Reverse(s.1));
+
+        self.results.extend(scored.into_iter().map(|(idx, _, positions)| ResultRow {
+            kind: ResultKind::Line(idx),
+            positions,
+        }));
+        true
+    }
+
+    /// Hand the query to the plugin whose prefix it starts with and show its latest answer.
+    /// Returns false when no plugin is triggered; the plugin owns the list otherwise.
+    fn push_plugin_results(&mut self) -> bool {
+        let Some((idx, query)) = plugins::find(&self.plugins, &self.search_query) else {
+            self.active_plugin = None;
+            return false;
+        };
+
+        let plugin = &mut self.plugins[idx];
+        let sent = self
+            .active_plugin
+            .as_ref()
+            .is_some_and(|(active, sent, _)| *active == idx && *sent == query);
+        if !sent {
+            plugin.query(&query);
         }
 
-        self.update_window();
+        let state = plugin.state();
+        self.results.extend(state.items.into_iter().map(|item| ResultRow {
+            kind: ResultKind::Plugin(idx, item),
+            positions: Vec::new(),
+        }));
+        self.active_plugin = Some((idx, query, state.generation));
+        true
+    }
+
+    /// Status line of the active plugin while it has no results to show
+    fn plugin_message(&self) -> Option<String> {
+        let (idx, _, _) = self.active_plugin.as_ref()?;
+        let plugin = &self.plugins[*idx];
+        Some(match plugin.state().status {
+            PluginStatus::Idle | PluginStatus::Waiting => format!("Waiting for {}...", plugin.config.name),
+            PluginStatus::Ready => format!("No results from {}", plugin.config.name),
+            PluginStatus::Failed(message) => message,
+        })
     }
 
-    fn update_window(&mut self) {
-        if self.filtered_indices.is_empty() {
-            self.window_start = 0;
+    /// SSH hosts when the query starts with the `ssh` keyword; the rest of it filters them
+    fn push_ssh_results(&mut self) {
+        let Some(hosts) = self.ssh_hosts.clone() else {
             return;
+        };
+        let query = self.search_query.trim_start();
+        let filter = match query.split_once(char::is_whitespace) {
+            Some((keyword, rest)) if keyword.eq_ignore_ascii_case(SSH_KEYWORD) => rest.trim().to_lowercase(),
+            _ => return,
+        };
+
+        let mut scored: Vec<(usize, f64, Vec<usize>)> = hosts
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, host)| {
+                if filter.is_empty() {
+                    return Some((idx, frecency::score(&host.id()), Vec::new()));
+                }
+                let (score, positions) = self.matcher.fuzzy_indices(&host.name_lower, &filter)?;
+                let bonus = frecency::bonus(&host.id());
+                Some((idx, (score + bonus) as f64, positions))
+            })
+            .collect();
+
+        // Stable sort keeps config order ahead of known_hosts for equal scores
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        self.results.extend(scored.into_iter().map(|(idx, _, positions)| ResultRow {
+            kind: ResultKind::Ssh(idx),
+            positions,
+        }));
+    }
+
+    /// Frecency key for PATH executables, kept apart from desktop file ids
+    fn executable_id(name: &str) -> String {
+        format!("path:{}", name)
+    }
+
+    /// Split `@category` filters out of the query, e.g. `@dev code` -> (Some("dev"), "code")
+    fn parse_query(query: &str) -> (Option<String>, String) {
+        let mut category = None;
+        let mut words = Vec::new();
+
+        for word in query.split_whitespace() {
+            match word.strip_prefix('@') {
+                Some(c) if !c.is_empty() => category = Some(normalize::fold(c)),
+                Some(_) => {}
+                None => words.push(word),
+            }
         }
 
-        if self.selected_index >= self.window_start + self.window_size {
-            self.window_start = self.selected_index + 1 - self.window_size;
-        } else if self.selected_index < self.window_start {
-            self.window_start = self.selected_index;
+        (category, words.join(" "))
+    }
+
+    /// Best match over GenericName, Keywords and Exec, then Categories and description at half weight
+    fn secondary_score(matcher: &SkimMatcherV2, app: &App, query: &str) -> Option<i64> {
+        let full = [
+            app.generic_name_lower.as_deref(),
+            app.keywords_lower.as_deref(),
+            app.exec_lower.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .filter_map(|field| matcher.fuzzy_match(field, query));
+
+        let half = app
+            .categories_lower
+            .iter()
+            .map(String::as_str)
+            .chain(app.description_lower.as_deref())
+            .filter_map(|field| matcher.fuzzy_match(field, query))
+            .map(|score| score / 2);
+
+        full.chain(half).max()
+    }
+
+    /// Split a name into runs of (text, highlighted) using matched char positions
+    pub(crate) fn highlight_segments(name: &str, positions: &[usize]) -> Vec<(String, bool)> {
+        let mut segments: Vec<(String, bool)> = Vec::new();
+
+        for (i, ch) in name.chars().enumerate() {
+            let highlighted = positions.contains(&i);
+            match segments.last_mut() {
+                Some((segment, h)) if *h == highlighted => segment.push(ch),
+                _ => segments.push((ch.to_string(), highlighted)),
+            }
         }
+
+        segments
+    }
+
+    /// Select the first result again, e.g. when the launcher is shown
+    pub fn reset_selection(&mut self) {
+        self.list.reset();
     }
 
     pub fn update(&mut self, message: Message) -> Task<Message> {
@@ -221,37 +892,201 @@ impl AppList {
             Message::SearchInput(query) => {
                 self.search_query = query;
                 self.filter_apps();
-                self.window_start = 0;
                 Task::none()
             }
             Message::ArrowUp => {
-                if self.selected_index > 0 {
-                    self.selected_index -= 1;
-                    self.update_window();
+                if let Some(expanded) = &mut self.expanded {
+                    if let Some(action) = expanded.selected {
+                        expanded.selected = action.checked_sub(1);
+                        return Task::none();
+                    }
+                    self.expanded = None;
                 }
+                self.list.up(self.results.len());
                 Task::none()
             }
             Message::ArrowDown => {
-                if !self.filtered_indices.is_empty()
-                    && self.selected_index < self.filtered_indices.len() - 1
-                {
-                    self.selected_index += 1;
-                    self.update_window();
+                if let Some(expanded) = &mut self.expanded {
+                    let next = expanded.selected.map_or(0, |a| a + 1);
+                    if next < expanded.actions.len() {
+                        expanded.selected = Some(next);
+                        return Task::none();
+                    }
+                    self.expanded = None;
                 }
+                self.list.down(self.results.len());
                 Task::none()
             }
-            Message::LaunchSelected => {
-                self.launch_selected();
+            Message::LaunchSelected => self.launch_selected(),
+            Message::ToggleActions => {
+                if self.expanded.is_some() {
+                    self.expanded = None;
+                } else {
+                    self.expand_actions();
+                }
                 Task::none()
             }
+            Message::PluginActivated { plugin, item, handled } => {
+                if !handled {
+                    eprintln!("[AppList] {} did not handle {:?}", plugin, item);
+                }
+                Task::none()
+            }
+        }
+    }
+
+    /// Whether launching the selection hands it to a plugin, answered with `PluginActivated`
+    pub fn is_plugin_selected(&self) -> bool {
+        matches!(self.results.get(self.list.selected()).map(|r| &r.kind), Some(ResultKind::Plugin(..)))
+    }
+
+    /// Load the Desktop Entry actions of the selected app
+    fn expand_actions(&mut self) {
+        let Some(ResultKind::App(app_idx)) = self.results.get(self.list.selected()).map(|r| &r.kind) else {
+            return;
+        };
+        let Some(info) = DesktopAppInfo::new(&self.apps[*app_idx].id) else {
+            return;
+        };
+
+        let actions: Vec<(String, String)> = info
+            .list_actions()
+            .into_iter()
+            .map(|action| {
+                let name = info.action_name(&action).to_string();
+                (action.to_string(), name)
+            })
+            .collect();
+
+        if actions.is_empty() {
+            return;
+        }
+
+        self.expanded = Some(ExpandedActions {
+            list_index: self.list.selected(),
+            actions,
+            selected: None,
+        });
+    }
+
+    fn launch_selected(&mut self) -> Task<Message> {
+        let Some(row) = self.results.get(self.list.selected()) else {
+            return Task::none();
+        };
+
+        match &row.kind {
+            ResultKind::App(app_idx) => self.launch_app(&self.apps[*app_idx]),
+            ResultKind::Executable(exe_idx, args) => {
+                if let Some(exe) = self.executables.as_ref().and_then(|e| e.get(*exe_idx)) {
+                    Self::run_executable(exe, args);
+                }
+            }
+            ResultKind::Conversion(conversion) => {
+                if let Err(e) = copy::copy_to_clipboard(&conversion.value) {
+                    eprintln!("[AppList] Failed to copy conversion: {}", e);
+                }
+            }
+            ResultKind::Recent(doc_idx) => {
+                if let Some(doc) = self.recent.as_ref().and_then(|d| d.get(*doc_idx)) {
+                    recent::open(doc);
+                }
+            }
+            ResultKind::Quicklink(link_idx, query) => {
+                if let Some(quicklink) = self.quicklinks.get(*link_idx) {
+                    quicklinks::open(quicklink, query);
+                }
+            }
+            ResultKind::Ssh(host_idx) => {
+                if let Some(host) = self.ssh_hosts.as_ref().and_then(|h| h.get(*host_idx)) {
+                    self.connect_ssh(host);
+                }
+            }
+            ResultKind::Plugin(plugin_idx, item) => {
+                let plugin = &mut self.plugins[*plugin_idx];
+                let name = plugin.config.name.clone();
+                let title = item.title.clone();
+                return Task::perform(plugin.activate(item), move |handled| Message::PluginActivated {
+                    plugin: name,
+                    item: title,
+                    handled,
+                });
+            }
+            // Printed by the caller, which owns the exit status
+            ResultKind::Line(_) => {}
         }
+        Task::none()
     }
 
-    fn launch_selected(&self) {
-        if let Some(&app_idx) = self.filtered_indices.get(self.selected_index) {
-            let app = &Self::all_apps()[app_idx];
-            if let Some(info) = DesktopAppInfo::new(&app.id) {
-                let _ = info.launch(&[], AppLaunchContext::NONE);
+    fn connect_ssh(&self, host: &SshHost) {
+        let Some(term) = terminal::resolve(self.terminal.as_deref()) else {
+            eprintln!("[AppList] No terminal emulator found for ssh {}", host.name);
+            return;
+        };
+
+        match terminal::spawn_in_terminal(&term, &host.command()) {
+            Ok(()) => frecency::record_launch(&host.id()),
+            Err(e) => eprintln!("[AppList] Failed to open ssh {} in {:?}: {}", host.name, term, e),
+        }
+    }
+
+    fn run_executable(exe: &Executable, args: &str) {
+        let result = std::process::Command::new(&exe.path)
+            .args(terminal::split_command(args))
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn();
+
+        match result {
+            Ok(child) => {
+                terminal::reap_in_background(child);
+                frecency::record_launch(&Self::executable_id(&exe.name));
+            }
+            Err(e) => eprintln!("[AppList] Failed to run {:?}: {}", exe.path, e),
+        }
+    }
+
+    fn launch_app(&self, app: &App) {
+        let Some(info) = DesktopAppInfo::new(&app.id) else {
+            return;
+        };
+
+        let action = self
+            .expanded
+            .as_ref()
+            .and_then(|e| e.selected.and_then(|a| e.actions.get(a)));
+
+        let launched = if let Some((action_id, _)) = action {
+            info.launch_action(action_id, AppLaunchContext::NONE);
+            true
+        } else if info.boolean("Terminal") {
+            self.launch_in_terminal(&info)
+        } else {
+            info.launch(&[], AppLaunchContext::NONE).is_ok()
+        };
+
+        if launched {
+            frecency::record_launch(&app.id);
+        }
+    }
+
+    /// Run the entry's Exec line in our terminal instead of relying on gio's terminal guessing
+    fn launch_in_terminal(&self, info: &DesktopAppInfo) -> bool {
+        let Some(exec) = info.string("Exec") else {
+            return false;
+        };
+        let command = terminal::strip_field_codes(terminal::split_command(&exec));
+
+        let Some(term) = terminal::resolve(self.terminal.as_deref()) else {
+            eprintln!("[AppList] No terminal emulator found - falling back to gio");
+            return info.launch(&[], AppLaunchContext::NONE).is_ok();
+        };
+
+        match terminal::spawn_in_terminal(&term, &command) {
+            Ok(()) => true,
+            Err(e) => {
+                eprintln!("[AppList] Failed to launch {:?} in {:?}: {}", command, term, e);
+                false
             }
         }
     }
@@ -264,8 +1099,34 @@ impl AppList {
     ) -> Element<'a, Message> {
         let mut items = column![].spacing(1);
         
-        // Show loading message if apps aren't loaded yet
-        if self.is_loading || Self::all_apps().is_empty() {
+        let plugin_message = self.plugin_message().filter(|_| self.results.is_empty());
+
+        if let Some(message) = plugin_message {
+            items = items.push(
+                container(
+                    text(message)
+                        .font(font)
+                        .size(font_size)
+                        .color(theme.color6)
+                )
+                .padding(20)
+                .width(Length::Fill)
+                .center_x(Length::Fill)
+            );
+        } else if self.dmenu.is_some() && self.results.is_empty() {
+            items = items.push(
+                container(
+                    text("No matching entries")
+                        .font(font)
+                        .size(font_size)
+                        .color(theme.color6)
+                )
+                .padding(20)
+                .width(Length::Fill)
+                .center_x(Length::Fill)
+            );
+        } else if (self.is_loading || self.apps.is_empty()) && self.results.is_empty() {
+            // Show loading message if apps aren't loaded yet
             items = items.push(
                 container(
                     text("Loading applications...")
@@ -277,7 +1138,7 @@ impl AppList {
                 .width(Length::Fill)
                 .center_x(Length::Fill)
             );
-        } else if self.filtered_indices.is_empty() {
+        } else if self.results.is_empty() {
             // Show "no results" if search yields nothing
             items = items.push(
                 container(
@@ -296,13 +1157,56 @@ impl AppList {
             );
         } else {
             // Normal app list rendering
-            let window_end = (self.window_start + self.window_size).min(self.filtered_indices.len());
-            let apps = Self::all_apps();
-
-            for idx in self.window_start..window_end {
-                let &app_idx = &self.filtered_indices[idx];
-                let app = &apps[app_idx];
-                let selected = idx == self.selected_index;
+            for idx in self.list.visible(self.results.len()) {
+                let result = &self.results[idx];
+                let (icon_name, title, subtitle) = match &result.kind {
+                    ResultKind::App(app_idx) => {
+                        let app = &self.apps[*app_idx];
+                        (app.icon.as_deref(), app.name.as_str(), "")
+                    }
+                    ResultKind::Executable(exe_idx, args) => {
+                        let exe = self.executables.as_ref().and_then(|e| e.get(*exe_idx));
+                        (
+                            Some("utilities-terminal"),
+                            exe.map_or("", |e| e.name.as_str()),
+                            args.as_str(),
+                        )
+                    }
+                    ResultKind::Conversion(conversion) => {
+                        (Some("accessories-calculator"), conversion.display.as_str(), "")
+                    }
+                    ResultKind::Recent(doc_idx) => {
+                        let doc = self.recent.as_ref().and_then(|d| d.get(*doc_idx));
+                        (
+                            Some("document-open-recent"),
+                            doc.map_or("", |d| d.name.as_str()),
+                            doc.and_then(|d| d.app.as_ref()).map_or("", |a| a.display_name.as_str()),
+                        )
+                    }
+                    ResultKind::Quicklink(link_idx, query) => {
+                        let quicklink = &self.quicklinks[*link_idx];
+                        let subtitle = if quicklink.takes_query() { query.as_str() } else { "" };
+                        (Some("web-browser"), quicklink.name.as_str(), subtitle)
+                    }
+                    ResultKind::Ssh(host_idx) => {
+                        let host = self.ssh_hosts.as_ref().and_then(|h| h.get(*host_idx));
+                        (
+                            Some("network-server"),
+                            host.map_or("", |h| h.name.as_str()),
+                            host.map_or("", |h| h.detail.as_str()),
+                        )
+                    }
+                    ResultKind::Plugin(_, item) => {
+                        (item.icon.as_deref(), item.title.as_str(), item.subtitle.as_str())
+                    }
+                    ResultKind::Line(line_idx) => {
+                        let line = self.dmenu.as_ref().map_or("", |lines| lines[*line_idx].0.as_str());
+                        (None, line, "")
+                    }
+                };
+                let expanded = self.expanded.as_ref().filter(|e| e.list_index == idx);
+                let selected = idx == self.list.selected()
+                    && expanded.is_none_or(|e| e.selected.is_none());
 
                 let bg = if selected {
                     Some(theme.color3.into())
@@ -316,20 +1220,54 @@ impl AppList {
                     theme.foreground
                 };
 
-                let content = if selected {
-                    row![
-                        text(">>").font(font).size(font_size).color(fg),
-                        text(&app.name).font(font).size(font_size).color(fg),
-                    ]
-                    .spacing(4)
+                let highlight = if selected {
+                    theme.color1
                 } else {
-                    row![
-                        text("  ").font(font).size(font_size).color(fg),
-                        text(&app.name).font(font).size(font_size).color(fg),
-                    ]
-                    .spacing(4)
+                    theme.color3
                 };
 
+                let mut name_row = row![].spacing(0);
+                for (segment, highlighted) in Self::highlight_segments(title, &result.positions) {
+                    name_row = name_row.push(
+                        text(segment)
+                            .font(font)
+                            .size(font_size)
+                            .color(if highlighted { highlight } else { fg }),
+                    );
+                }
+                if !subtitle.is_empty() {
+                    name_row = name_row.push(
+                        text(format!(" {}", subtitle))
+                            .font(font)
+                            .size(font_size)
+                            .color(if selected { fg } else { theme.color7 }),
+                    );
+                }
+
+                let marker = match (selected, expanded) {
+                    (true, _) => ">>",
+                    (false, Some(_)) => " v",
+                    (false, None) => "  ",
+                };
+
+                // Icons resolve in the background; keep the column aligned until they arrive
+                let icon_size = font_size + 4.0;
+                let icon: Element<'a, Message> = match icon_name.and_then(|i| self.icons.get(i)) {
+                    Some(path) => image(image::Handle::from_path(path))
+                        .width(Length::Fixed(icon_size))
+                        .height(Length::Fixed(icon_size))
+                        .into(),
+                    None => Space::new().width(Length::Fixed(icon_size)).into(),
+                };
+
+                let content = row![
+                    text(marker).font(font).size(font_size).color(fg),
+                    icon,
+                    name_row,
+                ]
+                .spacing(4)
+                .align_y(iced::Alignment::Center);
+
                 items = items.push(
                     container(content)
                         .padding([2, 4])
@@ -340,6 +1278,35 @@ impl AppList {
                             ..Default::default()
                         }),
                 );
+
+                // Actions submenu under the expanded app
+                if let Some(expanded) = expanded {
+                    for (action_idx, (_, action_name)) in expanded.actions.iter().enumerate() {
+                        let action_selected = expanded.selected == Some(action_idx);
+                        let fg = if action_selected { theme.background } else { theme.color7 };
+                        let bg = if action_selected { Some(theme.color3.into()) } else { None };
+
+                        items = items.push(
+                            container(
+                                row![
+                                    text(if action_selected { "  >>" } else { "    " })
+                                        .font(font)
+                                        .size(font_size)
+                                        .color(fg),
+                                    text(action_name).font(font).size(font_size).color(fg),
+                                ]
+                                .spacing(4),
+                            )
+                            .padding([2, 4])
+                            .width(Length::Fill)
+                            .style(move |_| container::Style {
+                                background: bg,
+                                border: Border::default(),
+                                ..Default::default()
+                            }),
+                        );
+                    }
+                }
             }
         }
 
@@ -347,32 +1314,7 @@ impl AppList {
             .id(self.scroll_id.clone())
             .width(Length::Fill)
             .height(Length::Fill)
-            .style(|_, _| scrollable::Style {
-                container: container::Style::default(),
-                vertical_rail: scrollable::Rail {
-                    background: None,
-                    border: Border::default(),
-                    scroller: scrollable::Scroller {
-                        background: iced::Background::Color(iced::Color::TRANSPARENT),
-                        border: Border::default(),
-                    },
-                },
-                horizontal_rail: scrollable::Rail {
-                    background: None,
-                    border: Border::default(),
-                    scroller: scrollable::Scroller {
-                        background: iced::Background::Color(iced::Color::TRANSPARENT),
-                        border: Border::default(),
-                    },
-                },
-                gap: None,
-                auto_scroll: scrollable::AutoScroll {
-                    background: iced::Background::Color(iced::Color::TRANSPARENT),
-                    border: Border::default(),
-                    icon: iced::Color::TRANSPARENT,
-                    shadow: iced::Shadow::default(),
-                },
-            })
+            .style(list_window::scrollbar_style(iced::Color::TRANSPARENT))
             .into()
     }
 }
\ No newline at end of file
@@ -0,0 +1,145 @@
+use iced::widget::{column, container, row, text};
+use iced::{Border, Element, Length};
+use std::collections::VecDeque;
+
+use crate::utils::calculator::{self, CalcEntry, CalcResult};
+use crate::utils::theme::Theme;
+use crate::Message;
+
+/// Evaluates `= expression` queries live; the result row sits above recent calculations
+pub struct CalculatorPanel {
+    expression: String,
+    result: Option<Result<CalcResult, String>>,
+    history: VecDeque<CalcEntry>,
+    /// 0 is the live result, 1.. index into `history`
+    selected_index: usize,
+}
+
+impl CalculatorPanel {
+    pub fn new() -> Self {
+        Self {
+            expression: String::new(),
+            result: None,
+            history: calculator::load_history(),
+            selected_index: 0,
+        }
+    }
+
+    pub fn set_expression(&mut self, expression: &str) {
+        let expression = expression.trim();
+        if expression == self.expression {
+            return;
+        }
+
+        self.expression = expression.to_string();
+        self.result = (!expression.is_empty()).then(|| calculator::evaluate(expression));
+        self.selected_index = 0;
+    }
+
+    pub fn arrow_up(&mut self) {
+        self.selected_index = self.selected_index.saturating_sub(1);
+    }
+
+    pub fn arrow_down(&mut self) {
+        if self.selected_index < self.history.len() {
+            self.selected_index += 1;
+        }
+    }
+
+    /// Value of the selected row, recording a fresh result in the history
+    pub fn take_selected(&mut self) -> Option<String> {
+        if self.selected_index == 0 {
+            let Some(Ok(result)) = &self.result else {
+                return None;
+            };
+            let value = result.value.clone();
+            calculator::push_history(
+                &mut self.history,
+                CalcEntry {
+                    expression: self.expression.clone(),
+                    result: value.clone(),
+                },
+            );
+            calculator::save_history(&self.history);
+            return Some(value);
+        }
+
+        self.history
+            .get(self.selected_index - 1)
+            .map(|entry| entry.result.clone())
+    }
+
+    pub fn view<'a>(&self, theme: &'a Theme, font: iced::Font, font_size: f32) -> Element<'a, Message> {
+        let small = font_size * 0.8;
+
+        let result_row: Element<'a, Message> = match &self.result {
+            None => text("Type an expression, e.g. = 0x1f + sqrt(2) * pi")
+                .font(font)
+                .size(small)
+                .color(theme.color7)
+                .into(),
+            Some(Err(error)) => text(error.clone())
+                .font(font)
+                .size(small)
+                .color(theme.color1)
+                .into(),
+            Some(Ok(result)) => {
+                let mut line = row![text(format!("= {}", result.value))
+                    .font(font)
+                    .size(font_size * 1.2)
+                    .color(theme.color2)]
+                .spacing(12)
+                .align_y(iced::Alignment::Center);
+                if let Some(alternate) = &result.alternate {
+                    line = line.push(text(alternate.clone()).font(font).size(small).color(theme.color7));
+                }
+                line.into()
+            }
+        };
+
+        let mut rows = column![Self::selectable(result_row, self.selected_index == 0, theme)].spacing(2);
+
+        if !self.history.is_empty() {
+            rows = rows.push(
+                container(text("History").font(font).size(small).color(theme.color6)).padding([6, 4]),
+            );
+        }
+        for (i, entry) in self.history.iter().enumerate() {
+            let selected = self.selected_index == i + 1;
+            let line = row![
+                text(entry.expression.clone())
+                    .font(font)
+                    .size(small)
+                    .color(theme.color7)
+                    .width(Length::Fill),
+                text(format!("= {}", entry.result))
+                    .font(font)
+                    .size(font_size)
+                    .color(if selected { theme.color3 } else { theme.foreground }),
+            ]
+            .spacing(8);
+            rows = rows.push(Self::selectable(line.into(), selected, theme));
+        }
+
+        rows = rows.push(
+            container(text("Enter: copy result").font(font).size(small).color(theme.color7)).padding([6, 4]),
+        );
+
+        container(rows).padding([2, 4]).width(Length::Fill).into()
+    }
+
+    fn selectable<'a>(content: Element<'a, Message>, selected: bool, theme: &'a Theme) -> Element<'a, Message> {
+        container(content)
+            .padding([2, 6])
+            .width(Length::Fill)
+            .style(move |_| container::Style {
+                border: Border {
+                    color: if selected { theme.color3 } else { iced::Color::TRANSPARENT },
+                    width: 1.0,
+                    radius: 0.0.into(),
+                },
+                ..Default::default()
+            })
+            .into()
+    }
+}
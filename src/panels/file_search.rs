@@ -0,0 +1,389 @@
+use gio::prelude::*;
+use gio::AppLaunchContext;
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use iced::widget::{column, row, text};
+use iced::Element;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use crate::panels::app_list::AppList;
+use crate::panels::list_window::{self, ListWindow};
+use crate::utils::copy;
+use crate::utils::file_index::{self, FileEntry, FileIndexOptions};
+use crate::utils::normalize;
+use crate::utils::theme::Theme;
+use crate::Message;
+
+/// Results kept per query; the index can hold hundreds of thousands of entries
+const MAX_RESULTS: usize = 50;
+/// Entries matched between checks for a newer search
+const CHUNK: usize = 4096;
+
+/// What Enter does with the selected file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileAction {
+    Open,
+    OpenFolder,
+    CopyPath,
+}
+
+const ACTIONS: [(FileAction, &str); 2] = [
+    (FileAction::OpenFolder, "Open containing folder"),
+    (FileAction::CopyPath, "Copy path"),
+];
+
+/// (index into the entries, matched positions in the file name)
+type Matches = Vec<(usize, Vec<usize>)>;
+
+struct Search {
+    id: u64,
+    query: String,
+    entries: Arc<Vec<FileEntry>>,
+}
+
+/// Background matching: each search gets a new id and the worker drops any it has been
+/// overtaken by, so typing never waits on a pass over the whole index
+struct Matcher {
+    jobs: Sender<Search>,
+    /// Id of the newest search
+    latest: Arc<AtomicU64>,
+    /// Newest finished search: its id and matches
+    found: Arc<Mutex<(u64, Matches)>>,
+}
+
+impl Matcher {
+    fn new() -> Self {
+        let (jobs, receiver) = mpsc::channel();
+        let latest = Arc::new(AtomicU64::new(0));
+        let found = Arc::new(Mutex::new((0, Vec::new())));
+
+        let (worker_latest, worker_found) = (Arc::clone(&latest), Arc::clone(&found));
+        thread::spawn(move || Self::run(receiver, &worker_latest, &worker_found));
+        Self { jobs, latest, found }
+    }
+
+    fn search(&self, query: &str, entries: Arc<Vec<FileEntry>>) {
+        let id = self.cancel();
+        let _ = self.jobs.send(Search {
+            id,
+            query: query.to_string(),
+            entries,
+        });
+    }
+
+    /// Make any running search obsolete; returns the id for the next one
+    fn cancel(&self) -> u64 {
+        self.latest.fetch_add(1, Ordering::AcqRel) + 1
+    }
+
+    /// Matches of the newest search once they are ready
+    fn take_finished(&self, shown: &mut u64) -> Option<Matches> {
+        let found = self.found.lock().unwrap();
+        if found.0 == *shown || found.0 != self.latest.load(Ordering::Acquire) {
+            return None;
+        }
+        *shown = found.0;
+        Some(found.1.clone())
+    }
+
+    fn run(jobs: Receiver<Search>, latest: &AtomicU64, found: &Mutex<(u64, Matches)>) {
+        let matcher = SkimMatcherV2::default();
+        while let Ok(mut search) = jobs.recv() {
+            // Only the last of several queued searches matters
+            while let Ok(newer) = jobs.try_recv() {
+                search = newer;
+            }
+            if let Some(matches) = Self::rank(&matcher, &search, latest) {
+                *found.lock().unwrap() = (search.id, matches);
+            }
+        }
+    }
+
+    /// Best MAX_RESULTS matches, or `None` when a newer search came in meanwhile
+    fn rank(matcher: &SkimMatcherV2, search: &Search, latest: &AtomicU64) -> Option<Matches> {
+        let query = normalize::fold(&search.query);
+        let mut scored: Vec<(usize, i64, Vec<usize>)> = Vec::new();
+
+        for (chunk_idx, chunk) in search.entries.chunks(CHUNK).enumerate() {
+            if latest.load(Ordering::Acquire) != search.id {
+                return None;
+            }
+            scored.extend(chunk.iter().enumerate().filter_map(|(idx, entry)| {
+                let (score, positions) = matcher.fuzzy_indices(&entry.name_lower, &query)?;
+                // Shallower paths win ties, they're usually what people mean
+                let depth = entry.path.components().count() as i64;
+                Some((chunk_idx * CHUNK + idx, score * 4 - depth, positions))
+            }));
+        }
+
+        scored.sort_by_key(|s| std::cmp::Reverse(s.1));
+        Some(
+            scored
+                .into_iter()
+                .take(MAX_RESULTS)
+                .map(|(idx, _, positions)| (idx, positions))
+                .collect(),
+        )
+    }
+}
+
+/// Searches the home directory index for `/ query`
+pub struct FileSearchPanel {
+    options: FileIndexOptions,
+    entries: Option<Arc<Vec<FileEntry>>>,
+    generation: u64,
+    query: String,
+    results: Matches,
+    matcher: Matcher,
+    /// Id of the search `results` come from
+    shown_search: u64,
+    /// Waiting for the matches of the current query
+    searching: bool,
+    list: ListWindow,
+    /// Actions submenu is open under the selected result
+    expanded: bool,
+    selected_action: Option<usize>,
+}
+
+impl FileSearchPanel {
+    pub fn new(options: FileIndexOptions) -> Self {
+        Self {
+            options,
+            entries: None,
+            generation: 0,
+            query: String::new(),
+            results: Vec::new(),
+            matcher: Matcher::new(),
+            shown_search: 0,
+            searching: false,
+            list: ListWindow::new(),
+            expanded: false,
+            selected_action: None,
+        }
+    }
+
+    pub fn set_query(&mut self, query: &str) {
+        // Indexing starts the first time file search is used
+        file_index::start(self.options.clone());
+
+        let query = query.trim();
+        if query == self.query && self.entries.is_some() {
+            return;
+        }
+        self.query = query.to_string();
+        self.list.reset();
+        self.filter();
+    }
+
+    /// Rescan home in the background if file search was used before
+    pub fn refresh_index(&self) {
+        file_index::refresh(self.options.clone());
+    }
+
+    /// Pick up a newer index from the background scan and finished searches;
+    /// call every frame while in file mode
+    pub fn check_index(&mut self) {
+        let generation = file_index::generation();
+        if generation != self.generation {
+            self.generation = generation;
+            self.entries = file_index::entries();
+            // Shown results index into the old entries
+            self.results.clear();
+            self.filter();
+        }
+
+        if let Some(matches) = self.matcher.take_finished(&mut self.shown_search) {
+            self.results = matches;
+            self.searching = false;
+            self.expanded = false;
+            self.selected_action = None;
+            self.list.clamp(self.results.len());
+        }
+    }
+
+    /// Start matching the query; the last results stay up until `check_index` picks up the new ones
+    fn filter(&mut self) {
+        let Some(entries) = self.entries.clone() else {
+            return;
+        };
+        if self.query.is_empty() {
+            self.matcher.cancel();
+            self.results.clear();
+            self.searching = false;
+            self.expanded = false;
+            self.selected_action = None;
+            self.list.clamp(0);
+            return;
+        }
+
+        self.matcher.search(&self.query, entries);
+        self.searching = true;
+    }
+
+    pub fn arrow_up(&mut self) {
+        if self.expanded {
+            match self.selected_action {
+                Some(a) => {
+                    self.selected_action = a.checked_sub(1);
+                    return;
+                }
+                None => self.expanded = false,
+            }
+        }
+        self.list.up(self.results.len());
+    }
+
+    pub fn arrow_down(&mut self) {
+        if self.expanded {
+            let next = self.selected_action.map_or(0, |a| a + 1);
+            if next < ACTIONS.len() {
+                self.selected_action = Some(next);
+                return;
+            }
+            self.expanded = false;
+            self.selected_action = None;
+        }
+        self.list.down(self.results.len());
+    }
+
+    /// Tab: show or hide the actions of the selected result
+    pub fn toggle_actions(&mut self) {
+        if self.results.is_empty() {
+            return;
+        }
+        self.expanded = !self.expanded;
+        self.selected_action = None;
+    }
+
+    fn selected_path(&self) -> Option<&Path> {
+        let (idx, _) = self.results.get(self.list.selected())?;
+        self.entries.as_ref()?.get(*idx).map(|e| e.path.as_path())
+    }
+
+    /// Run the selected action (opening the file by default); true when something happened
+    pub fn activate(&self) -> bool {
+        let Some(path) = self.selected_path() else {
+            return false;
+        };
+        let action = match self.selected_action.filter(|_| self.expanded) {
+            Some(a) => ACTIONS[a].0,
+            None => FileAction::Open,
+        };
+
+        match action {
+            FileAction::Open => Self::open(path),
+            FileAction::OpenFolder => path.parent().is_some_and(Self::open),
+            FileAction::CopyPath => match copy::copy_to_clipboard(&path.to_string_lossy()) {
+                Ok(()) => true,
+                Err(e) => {
+                    eprintln!("[Files] Failed to copy path: {}", e);
+                    false
+                }
+            },
+        }
+    }
+
+    fn open(path: &Path) -> bool {
+        let uri = gio::File::for_path(path).uri();
+        match gio::AppInfo::launch_default_for_uri(&uri, AppLaunchContext::NONE) {
+            Ok(()) => true,
+            Err(e) => {
+                eprintln!("[Files] Failed to open {:?}: {}", path, e);
+                false
+            }
+        }
+    }
+
+    /// `/home/me/docs` -> `~/docs`
+    fn display_dir(path: &Path) -> String {
+        let parent = path.parent().map(PathBuf::from).unwrap_or_default();
+        match dirs::home_dir().and_then(|home| parent.strip_prefix(&home).ok().map(PathBuf::from)) {
+            Some(relative) if relative.as_os_str().is_empty() => "~".to_string(),
+            Some(relative) => format!("~/{}", relative.display()),
+            None => parent.display().to_string(),
+        }
+    }
+
+    pub fn view<'a>(&self, theme: &'a Theme, font: iced::Font, font_size: f32) -> Element<'a, Message> {
+        let small = font_size * 0.8;
+
+        let Some(entries) = self.entries.as_ref() else {
+            return list_window::placeholder("Indexing home directory...", theme, font, font_size);
+        };
+        if self.query.is_empty() {
+            return list_window::placeholder("Type to search files and folders", theme, font, font_size);
+        }
+        if self.results.is_empty() {
+            let message = if self.searching { "Searching..." } else { "No matching files" };
+            return list_window::placeholder(message, theme, font, font_size);
+        }
+
+        let mut items = column![].spacing(1);
+        for list_idx in self.list.visible(self.results.len()) {
+            let (entry_idx, positions) = &self.results[list_idx];
+            let entry = &entries[*entry_idx];
+            let selected = list_idx == self.list.selected() && (!self.expanded || self.selected_action.is_none());
+            let fg = if selected { theme.background } else { theme.foreground };
+            let highlight = if selected { theme.color1 } else { theme.color3 };
+            let bg = if selected { Some(theme.color3.into()) } else { None };
+
+            let marker = match (selected, self.expanded && list_idx == self.list.selected()) {
+                (true, _) => ">>",
+                (false, true) => " v",
+                (false, false) => "  ",
+            };
+
+            let name = entry
+                .path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            let mut name_row = row![].spacing(0);
+            for (segment, highlighted) in AppList::highlight_segments(&name, positions) {
+                name_row = name_row.push(
+                    text(segment)
+                        .font(font)
+                        .size(font_size)
+                        .color(if highlighted { highlight } else { fg }),
+                );
+            }
+            if entry.is_dir {
+                name_row = name_row.push(text("/").font(font).size(font_size).color(fg));
+            }
+            name_row = name_row.push(
+                text(format!("  {}", Self::display_dir(&entry.path)))
+                    .font(font)
+                    .size(small)
+                    .color(if selected { fg } else { theme.color7 }),
+            );
+
+            let content = row![text(marker).font(font).size(font_size).color(fg), name_row].spacing(4);
+            items = items.push(list_window::row(content, bg));
+
+            if self.expanded && list_idx == self.list.selected() {
+                for (action_idx, (_, label)) in ACTIONS.iter().enumerate() {
+                    let action_selected = self.selected_action == Some(action_idx);
+                    let fg = if action_selected { theme.background } else { theme.color7 };
+                    let bg = if action_selected { Some(theme.color3.into()) } else { None };
+
+                    let content = row![
+                        text(if action_selected { "  >>" } else { "    " })
+                            .font(font)
+                            .size(font_size)
+                            .color(fg),
+                        text(*label).font(font).size(font_size).color(fg),
+                    ]
+                    .spacing(4);
+                    items = items.push(list_window::row(content, bg));
+                }
+            }
+        }
+
+        items.into()
+    }
+}
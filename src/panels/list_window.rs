@@ -0,0 +1,165 @@
+//! Pieces shared by the result lists of the search modes: the selection with the rows shown
+//! around it, and the look of rows, placeholders and scrollbars.
+
+use iced::widget::{container, scrollable, text, Container};
+use iced::{Background, Border, Color, Element, Length};
+use std::ops::Range;
+
+use crate::utils::theme::Theme;
+use crate::Message;
+
+/// Rows a list shows at once
+const WINDOW_SIZE: usize = 17;
+
+/// Selected row of a list and the slice of rows on screen, which follows the selection
+#[derive(Debug, Clone, Default)]
+pub struct ListWindow {
+    selected: usize,
+    start: usize,
+}
+
+impl ListWindow {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Select the first row, e.g. for a new query
+    pub fn reset(&mut self) {
+        self.selected = 0;
+        self.start = 0;
+    }
+
+    /// Keep the selection valid for a list that now has `len` rows
+    pub fn clamp(&mut self, len: usize) {
+        if self.selected >= len {
+            self.selected = 0;
+        }
+        self.scroll(len);
+    }
+
+    /// Move the selection up; false when it already is on the first row
+    pub fn up(&mut self, len: usize) -> bool {
+        if self.selected == 0 {
+            return false;
+        }
+        self.selected -= 1;
+        self.scroll(len);
+        true
+    }
+
+    /// Move the selection down; false when it already is on the last row
+    pub fn down(&mut self, len: usize) -> bool {
+        if self.selected + 1 >= len {
+            return false;
+        }
+        self.selected += 1;
+        self.scroll(len);
+        true
+    }
+
+    /// Rows to draw out of `len`
+    pub fn visible(&self, len: usize) -> Range<usize> {
+        self.start.min(len)..(self.start + WINDOW_SIZE).min(len)
+    }
+
+    fn scroll(&mut self, len: usize) {
+        if len == 0 {
+            self.start = 0;
+        } else if self.selected >= self.start + WINDOW_SIZE {
+            self.start = self.selected + 1 - WINDOW_SIZE;
+        } else if self.selected < self.start {
+            self.start = self.selected;
+        }
+    }
+}
+
+/// One row of a result list, highlighted with `background` when selected
+pub fn row<'a>(content: impl Into<Element<'a, Message>>, background: Option<Background>) -> Container<'a, Message> {
+    container(content)
+        .padding([2, 4])
+        .width(Length::Fill)
+        .style(move |_| container::Style {
+            background,
+            border: Border::default(),
+            ..Default::default()
+        })
+}
+
+/// Centered message shown instead of an empty list
+pub fn placeholder<'a>(
+    message: impl text::IntoFragment<'a>,
+    theme: &Theme,
+    font: iced::Font,
+    font_size: f32,
+) -> Element<'a, Message> {
+    container(text(message).font(font).size(font_size).color(theme.color6))
+        .padding(20)
+        .width(Length::Fill)
+        .center_x(Length::Fill)
+        .into()
+}
+
+/// Scrollbars without rails; only a vertical scroller in `scroller`, if it isn't transparent
+pub fn scrollbar_style(scroller: Color) -> impl Fn(&iced::Theme, scrollable::Status) -> scrollable::Style {
+    move |_, _| {
+        let rail = |color: Color| scrollable::Rail {
+            background: None,
+            border: Border::default(),
+            scroller: scrollable::Scroller {
+                background: Background::Color(color),
+                border: Border::default(),
+            },
+        };
+        scrollable::Style {
+            container: container::Style::default(),
+            vertical_rail: rail(scroller),
+            horizontal_rail: rail(Color::TRANSPARENT),
+            gap: None,
+            auto_scroll: scrollable::AutoScroll {
+                background: Background::Color(Color::TRANSPARENT),
+                border: Border::default(),
+                icon: Color::TRANSPARENT,
+                shadow: iced::Shadow::default(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_follows_the_selection() {
+        let mut list = ListWindow::new();
+        assert_eq!(list.visible(5), 0..5);
+        assert!(!list.up(5));
+
+        for _ in 0..WINDOW_SIZE {
+            assert!(list.down(40));
+        }
+        assert_eq!(list.selected(), WINDOW_SIZE);
+        assert_eq!(list.visible(40), 1..WINDOW_SIZE + 1);
+
+        for _ in 0..WINDOW_SIZE {
+            list.up(40);
+        }
+        assert_eq!(list.visible(40), 0..WINDOW_SIZE);
+    }
+
+    #[test]
+    fn clamp_resets_a_selection_past_the_end() {
+        let mut list = ListWindow::new();
+        while list.down(30) {}
+        assert_eq!(list.selected(), 29);
+        assert!(!list.down(30));
+
+        list.clamp(3);
+        assert_eq!(list.selected(), 0);
+        assert_eq!(list.visible(3), 0..3);
+    }
+}
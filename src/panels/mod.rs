@@ -11,4 +11,11 @@ pub mod services;
 pub mod system_services;
 pub mod services_bottom_row;
 pub mod clipboard_panel;
-pub mod wallpaper_panel;
\ No newline at end of file
+pub mod wallpaper_panel;
+pub mod shell;
+pub mod calculator;
+pub mod file_search;
+pub mod recent;
+pub mod symbols;
+pub mod windows;
+pub mod list_window;
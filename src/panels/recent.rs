@@ -0,0 +1,144 @@
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use iced::widget::{column, row, text};
+use iced::Element;
+use std::sync::Arc;
+
+use crate::panels::app_list::AppList;
+use crate::panels::list_window::{self, ListWindow};
+use crate::utils::normalize;
+use crate::utils::recent::{self, RecentDocument};
+use crate::utils::theme::Theme;
+use crate::Message;
+
+/// Lists recently used documents for `~ query`, newest first
+pub struct RecentPanel {
+    documents: Option<Arc<Vec<RecentDocument>>>,
+    query: String,
+    /// (index into `documents`, matched positions in the file name)
+    results: Vec<(usize, Vec<usize>)>,
+    matcher: SkimMatcherV2,
+    list: ListWindow,
+}
+
+impl RecentPanel {
+    pub fn new() -> Self {
+        Self {
+            documents: None,
+            query: String::new(),
+            results: Vec::new(),
+            matcher: SkimMatcherV2::default(),
+            list: ListWindow::new(),
+        }
+    }
+
+    pub fn set_query(&mut self, query: &str) {
+        recent::start();
+
+        self.query = query.trim().to_string();
+        self.list.reset();
+        self.filter();
+    }
+
+    /// Pick up the documents once loaded or reloaded; call every frame while in recent mode
+    pub fn check_loaded(&mut self) {
+        let Some(documents) = recent::documents() else {
+            return;
+        };
+        if self.documents.as_ref().is_none_or(|current| !Arc::ptr_eq(current, &documents)) {
+            self.documents = Some(documents);
+            self.filter();
+        }
+    }
+
+    fn filter(&mut self) {
+        self.results.clear();
+        let Some(documents) = self.documents.clone() else {
+            return;
+        };
+
+        if self.query.is_empty() {
+            self.results = (0..documents.len()).map(|idx| (idx, Vec::new())).collect();
+        } else {
+            let query = normalize::fold(&self.query);
+            let mut scored: Vec<(usize, i64, Vec<usize>)> = documents
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, doc)| {
+                    let (score, positions) = self.matcher.fuzzy_indices(&doc.name_lower, &query)?;
+                    Some((idx, score, positions))
+                })
+                .collect();
+            // Stable sort keeps newer documents first among equal scores
+            scored.sort_by_key(|s| std::cmp::Reverse(s.1));
+            self.results = scored.into_iter().map(|(idx, _, positions)| (idx, positions)).collect();
+        }
+
+        self.list.clamp(self.results.len());
+    }
+
+    pub fn arrow_up(&mut self) {
+        self.list.up(self.results.len());
+    }
+
+    pub fn arrow_down(&mut self) {
+        self.list.down(self.results.len());
+    }
+
+    /// Open the selected document; true when it was launched
+    pub fn activate(&self) -> bool {
+        let Some((idx, _)) = self.results.get(self.list.selected()) else {
+            return false;
+        };
+        self.documents
+            .as_ref()
+            .and_then(|d| d.get(*idx))
+            .is_some_and(recent::open)
+    }
+
+    pub fn view<'a>(&self, theme: &'a Theme, font: iced::Font, font_size: f32) -> Element<'a, Message> {
+        let small = font_size * 0.8;
+
+        let Some(documents) = self.documents.as_ref() else {
+            return list_window::placeholder("Loading recent documents...", theme, font, font_size);
+        };
+        if self.results.is_empty() {
+            let message = if self.query.is_empty() { "No recent documents" } else { "No matching documents" };
+            return list_window::placeholder(message, theme, font, font_size);
+        }
+
+        let mut items = column![].spacing(1);
+        for list_idx in self.list.visible(self.results.len()) {
+            let (doc_idx, positions) = &self.results[list_idx];
+            let doc = &documents[*doc_idx];
+            let selected = list_idx == self.list.selected();
+            let fg = if selected { theme.background } else { theme.foreground };
+            let highlight = if selected { theme.color1 } else { theme.color3 };
+            let bg = if selected { Some(theme.color3.into()) } else { None };
+
+            let mut name_row = row![text(if selected { ">>" } else { "  " }).font(font).size(font_size).color(fg)]
+                .spacing(0);
+            name_row = name_row.push(text(" ").font(font).size(font_size));
+            for (segment, highlighted) in AppList::highlight_segments(&doc.name, positions) {
+                name_row = name_row.push(
+                    text(segment)
+                        .font(font)
+                        .size(font_size)
+                        .color(if highlighted { highlight } else { fg }),
+                );
+            }
+            if let Some(app) = &doc.app {
+                name_row = name_row.push(
+                    text(format!("  {}", app.display_name))
+                        .font(font)
+                        .size(small)
+                        .color(if selected { fg } else { theme.color7 }),
+                );
+            }
+
+            items = items.push(list_window::row(name_row, bg));
+        }
+
+        items.into()
+    }
+}
@@ -3,7 +3,6 @@ use iced::{Element, Border, Color, Length};
 use crate::utils::theme::Theme;
 use crate::Message;
 use crate::panels::search_bar::SearchBar;
-use crate::panels::app_list::AppList;
 use crate::panels::clock;
 use crate::panels::weather;
 use crate::panels::music;
@@ -22,7 +21,7 @@ pub fn right_main_panels_view<'a>(
     font: iced::Font,
     font_size: f32,
     search_bar: &'a SearchBar,
-    app_list: &'a AppList,
+    results: Element<'a, Message>,
     current_panel: crate::Panel,
     weather_panel: &'a weather::WeatherPanel,
     music_player: &'a MusicPlayer,
@@ -62,7 +61,7 @@ pub fn right_main_panels_view<'a>(
                         container(
                             container(
                                 container(
-                                    app_list.view(theme, font, font_size).map(Message::AppListMessage)
+                                    results
                                 )
                                 .width(Length::Fill)
                                 .height(Length::Fill)
@@ -10,6 +10,7 @@ pub enum Message {
 pub struct SearchBar {
     pub input_value: String,
     pub input_id: Id,
+    placeholder: String,
 }
 
 impl SearchBar {
@@ -17,12 +18,18 @@ impl SearchBar {
         Self {
             input_value: String::new(),
             input_id: Id::unique(),
+            placeholder: "Search for apps...".to_string(),
         }
     }
 
+    pub fn with_placeholder(mut self, placeholder: String) -> Self {
+        self.placeholder = placeholder;
+        self
+    }
+
     pub fn view<'a>(&self, theme: &'a crate::utils::theme::Theme, font: iced::Font, font_size: f32) -> Element<'a, Message> {
         text_input(
-            "Search for apps...",
+            &self.placeholder,
             &self.input_value,
         )
         .on_input(Message::InputChanged)
@@ -0,0 +1,316 @@
+use iced::widget::{column, container, row, scrollable, text};
+use iced::{Element, Length};
+use std::io::{self, BufRead, BufReader, Read};
+use std::os::unix::process::CommandExt;
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::panels::list_window;
+use crate::utils::terminal;
+use crate::utils::theme::Theme;
+use crate::Message;
+
+/// Output beyond this is dropped so a chatty command can't balloon memory or layout
+const MAX_OUTPUT_LINES: usize = 500;
+/// Longer lines are cut, so output without newlines can't grow memory either
+const MAX_LINE_BYTES: usize = 4096;
+/// How often the waiter checks whether the command has exited
+const EXIT_POLL: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Clone)]
+struct ShellOutput {
+    run_id: u64,
+    command: String,
+    /// (line, is_stderr)
+    lines: Vec<(String, bool)>,
+    truncated: bool,
+    /// `None` while running, or when killed by a signal
+    exit_code: Option<i32>,
+    finished: bool,
+    started: Instant,
+}
+
+/// Runs `> command` queries through `$SHELL -c` and shows their output in place of the app list
+pub struct ShellPanel {
+    output: Arc<Mutex<Option<ShellOutput>>>,
+    /// Process of the shown command with its run id, until it exits or is killed
+    child: Arc<Mutex<Option<(u64, Child)>>>,
+    next_run_id: u64,
+}
+
+impl ShellPanel {
+    pub fn new() -> Self {
+        Self {
+            output: Arc::new(Mutex::new(None)),
+            child: Arc::new(Mutex::new(None)),
+            next_run_id: 0,
+        }
+    }
+
+    fn shell() -> String {
+        std::env::var("SHELL")
+            .ok()
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| "/bin/sh".to_string())
+    }
+
+    /// Run `command` in the background, streaming stdout and stderr into the result pane.
+    /// A command still running from before is killed.
+    pub fn run(&mut self, command: &str) {
+        let command = command.trim().to_string();
+        if command.is_empty() {
+            return;
+        }
+        self.stop();
+
+        self.next_run_id += 1;
+        let run_id = self.next_run_id;
+
+        *self.output.lock().unwrap() = Some(ShellOutput {
+            run_id,
+            command: command.clone(),
+            lines: Vec::new(),
+            truncated: false,
+            exit_code: None,
+            finished: false,
+            started: Instant::now(),
+        });
+
+        let spawned = Command::new(Self::shell())
+            .arg("-c")
+            .arg(&command)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            // Its own process group, so `stop` reaches pipelines and background jobs too
+            .process_group(0)
+            .spawn();
+        let mut child = match spawned {
+            Ok(child) => child,
+            Err(e) => {
+                if let Some(state) = self.output.lock().unwrap().as_mut() {
+                    state.lines = vec![(format!("Failed to start {}: {}", Self::shell(), e), true)];
+                    state.finished = true;
+                }
+                return;
+            }
+        };
+
+        let readers: Vec<_> = [
+            child.stdout.take().map(|out| (Box::new(out) as Box<dyn Read + Send>, false)),
+            child.stderr.take().map(|err| (Box::new(err) as Box<dyn Read + Send>, true)),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|(stream, is_stderr)| {
+            let output = Arc::clone(&self.output);
+            thread::spawn(move || Self::read_output(stream, is_stderr, run_id, &output))
+        })
+        .collect();
+        *self.child.lock().unwrap() = Some((run_id, child));
+
+        let output = Arc::clone(&self.output);
+        let child = Arc::clone(&self.child);
+        thread::spawn(move || {
+            for reader in readers {
+                let _ = reader.join();
+            }
+            let exit_code = Self::wait(&child, run_id);
+
+            let mut guard = output.lock().unwrap();
+            // A newer command replaced this one while it ran
+            if let Some(state) = guard.as_mut().filter(|s| s.run_id == run_id) {
+                state.exit_code = exit_code;
+                state.finished = true;
+            }
+        });
+    }
+
+    /// Append lines from `stream` to the run's output until EOF. Past MAX_OUTPUT_LINES they are
+    /// read and dropped, so the command isn't blocked on a full pipe.
+    fn read_output(stream: impl Read, is_stderr: bool, run_id: u64, output: &Mutex<Option<ShellOutput>>) {
+        let mut reader = BufReader::new(stream);
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            if !matches!(Self::read_line(&mut reader, &mut line), Ok(n) if n > 0) {
+                return;
+            }
+
+            let mut guard = output.lock().unwrap();
+            // Replaced or cleared: stop reading, which closes the pipe on the command
+            let Some(state) = guard.as_mut().filter(|s| s.run_id == run_id) else {
+                return;
+            };
+            if state.lines.len() < MAX_OUTPUT_LINES {
+                let text = String::from_utf8_lossy(line.strip_suffix(b"\r").unwrap_or(&line));
+                state.lines.push((text.into_owned(), is_stderr));
+            } else {
+                state.truncated = true;
+            }
+        }
+    }
+
+    /// Read one line into `line`, keeping at most MAX_LINE_BYTES of it.
+    /// Returns the number of bytes consumed, 0 at EOF.
+    fn read_line(reader: &mut impl BufRead, line: &mut Vec<u8>) -> io::Result<usize> {
+        let mut consumed = 0;
+        loop {
+            let available = reader.fill_buf()?;
+            if available.is_empty() {
+                return Ok(consumed);
+            }
+
+            let (used, done) = match available.iter().position(|&b| b == b'\n') {
+                Some(newline) => (newline + 1, true),
+                None => (available.len(), false),
+            };
+            let content = if done { used - 1 } else { used };
+            let keep = content.min(MAX_LINE_BYTES.saturating_sub(line.len()));
+            line.extend_from_slice(&available[..keep]);
+
+            reader.consume(used);
+            consumed += used;
+            if done {
+                return Ok(consumed);
+            }
+        }
+    }
+
+    /// Exit code of run `run_id` once it exits; `None` if it was killed or replaced
+    fn wait(child: &Mutex<Option<(u64, Child)>>, run_id: u64) -> Option<i32> {
+        loop {
+            {
+                let mut guard = child.lock().unwrap();
+                let (id, process) = guard.as_mut()?;
+                if *id != run_id {
+                    return None;
+                }
+                match process.try_wait() {
+                    Ok(Some(status)) => {
+                        guard.take();
+                        return status.code();
+                    }
+                    Ok(None) => {}
+                    Err(_) => {
+                        guard.take();
+                        return None;
+                    }
+                }
+            }
+            thread::sleep(EXIT_POLL);
+        }
+    }
+
+    /// Kill the command started by `run` if it is still running, along with everything it started.
+    /// Leftover processes would hold the output pipes open and keep the reader threads alive.
+    fn stop(&mut self) {
+        if let Some((_, child)) = self.child.lock().unwrap().take() {
+            // The shell leads the group and is not reaped yet, so its id still names the group
+            unsafe {
+                libc::killpg(child.id() as libc::pid_t, libc::SIGKILL);
+            }
+            terminal::reap_in_background(child);
+        }
+    }
+
+    /// Start `command` detached from the launcher, ignoring its output
+    pub fn run_detached(&self, command: &str) {
+        let command = command.trim();
+        if command.is_empty() {
+            return;
+        }
+
+        match Command::new(Self::shell())
+            .arg("-c")
+            .arg(command)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        {
+            Ok(child) => terminal::reap_in_background(child),
+            Err(e) => eprintln!("[Shell] Failed to run {:?}: {}", command, e),
+        }
+    }
+
+    /// Kill the running command and forget the last result, e.g. when leaving shell mode
+    pub fn clear(&mut self) {
+        self.stop();
+        *self.output.lock().unwrap() = None;
+    }
+
+    pub fn view<'a>(&self, theme: &'a Theme, font: iced::Font, font_size: f32) -> Element<'a, Message> {
+        let output = self.output.lock().unwrap().clone();
+        let small = font_size * 0.8;
+
+        let Some(output) = output else {
+            return container(
+                column![
+                    text("Shell command").font(font).size(font_size).color(theme.color6),
+                    text("Enter: run and show output").font(font).size(small).color(theme.color7),
+                    text("Ctrl+Enter: run detached and close").font(font).size(small).color(theme.color7),
+                ]
+                .spacing(4),
+            )
+            .padding(20)
+            .width(Length::Fill)
+            .center_x(Length::Fill)
+            .into();
+        };
+
+        let status = if !output.finished {
+            format!("running... {}s", output.started.elapsed().as_secs())
+        } else {
+            match output.exit_code {
+                Some(code) => format!("exit {}", code),
+                None => "terminated".to_string(),
+            }
+        };
+        let status_color = match output.exit_code {
+            Some(0) => theme.color2,
+            _ if !output.finished => theme.color6,
+            _ => theme.color1,
+        };
+
+        let header = row![
+            text(format!("$ {}", output.command))
+                .font(font)
+                .size(font_size)
+                .color(theme.color3)
+                .width(Length::Fill),
+            text(status).font(font).size(small).color(status_color),
+        ]
+        .spacing(8);
+
+        let mut lines = column![].spacing(0);
+        for (line, is_stderr) in output.lines {
+            lines = lines.push(
+                text(line)
+                    .font(font)
+                    .size(small)
+                    .color(if is_stderr { theme.color1 } else { theme.foreground }),
+            );
+        }
+        if output.truncated {
+            lines = lines.push(
+                text(format!("... output truncated to {} lines", MAX_OUTPUT_LINES))
+                    .font(font)
+                    .size(small)
+                    .color(theme.color7),
+            );
+        }
+
+        column![
+            container(header).padding([2, 4]),
+            scrollable(container(lines).padding([2, 4]))
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .style(list_window::scrollbar_style(theme.color8)),
+        ]
+        .spacing(4)
+        .into()
+    }
+}
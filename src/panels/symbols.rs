@@ -0,0 +1,278 @@
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use iced::widget::{column, row, text};
+use iced::{Element, Length};
+use std::collections::HashMap;
+
+use crate::panels::app_list::AppList;
+use crate::panels::list_window::{self, ListWindow};
+use crate::utils::copy;
+use crate::utils::symbols::{self, Symbol};
+use crate::utils::theme::Theme;
+use crate::Message;
+
+/// Results kept per query
+const MAX_RESULTS: usize = 200;
+
+#[derive(Debug, Clone)]
+struct SymbolRow {
+    /// Glyph to copy; a recently used skin tone replaces the default one
+    glyph: String,
+    /// Index into `symbols::table()`
+    symbol: usize,
+    /// Matched character positions in the name
+    positions: Vec<usize>,
+}
+
+/// Emoji and Unicode symbol picker for `: query`
+pub struct SymbolPanel {
+    /// Recently picked glyphs, newest first
+    recent: Vec<String>,
+    /// Glyph -> table index, including skin tone variants of the emoji
+    by_glyph: HashMap<String, usize>,
+    results: Vec<SymbolRow>,
+    matcher: SkimMatcherV2,
+    list: ListWindow,
+    /// Skin tone variants of the selected emoji, shown after Tab
+    variants: Vec<String>,
+    selected_variant: Option<usize>,
+}
+
+impl SymbolPanel {
+    pub fn new() -> Self {
+        Self {
+            recent: symbols::load_recent(),
+            by_glyph: HashMap::new(),
+            results: Vec::new(),
+            matcher: SkimMatcherV2::default(),
+            list: ListWindow::new(),
+            variants: Vec::new(),
+            selected_variant: None,
+        }
+    }
+
+    fn index_glyphs(&mut self) {
+        if !self.by_glyph.is_empty() {
+            return;
+        }
+        for (idx, symbol) in symbols::table().iter().enumerate() {
+            self.by_glyph.insert(symbol.glyph.clone(), idx);
+            for variant in symbol.skin_tone_variants() {
+                self.by_glyph.insert(variant, idx);
+            }
+        }
+    }
+
+    pub fn set_query(&mut self, query: &str) {
+        self.index_glyphs();
+        let table = symbols::table();
+        let query = query.trim().to_lowercase();
+
+        self.results.clear();
+        self.variants.clear();
+        self.selected_variant = None;
+        self.list.reset();
+
+        // Recently used glyph for each symbol, so a picked skin tone sticks
+        let mut preferred: HashMap<usize, (usize, &str)> = HashMap::new();
+        for (rank, glyph) in self.recent.iter().enumerate() {
+            if let Some(&idx) = self.by_glyph.get(glyph) {
+                preferred.entry(idx).or_insert((rank, glyph.as_str()));
+            }
+        }
+        let glyph_for = |idx: usize| -> String {
+            preferred
+                .get(&idx)
+                .map_or_else(|| table[idx].glyph.clone(), |(_, g)| g.to_string())
+        };
+
+        if query.is_empty() {
+            let mut recent: Vec<(usize, usize)> = preferred.iter().map(|(idx, (rank, _))| (*rank, *idx)).collect();
+            recent.sort_unstable();
+            let recent_idx: Vec<usize> = recent.into_iter().map(|(_, idx)| idx).collect();
+
+            self.results = recent_idx
+                .iter()
+                .copied()
+                .chain((0..table.len()).filter(|idx| !recent_idx.contains(idx)))
+                .take(MAX_RESULTS)
+                .map(|idx| SymbolRow {
+                    glyph: glyph_for(idx),
+                    symbol: idx,
+                    positions: Vec::new(),
+                })
+                .collect();
+        } else {
+            let matcher = &self.matcher;
+            let mut scored: Vec<(usize, i64, Vec<usize>)> = table
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, symbol)| {
+                    let (score, positions) = Self::score(matcher, symbol, &query)?;
+                    // Recently used symbols first, newest strongest
+                    let bonus = preferred
+                        .get(&idx)
+                        .map_or(0, |(rank, _)| 200 - *rank as i64 * 5);
+                    Some((idx, score + bonus, positions))
+                })
+                .collect();
+
+            scored.sort_by_key(|s| std::cmp::Reverse(s.1));
+            self.results = scored
+                .into_iter()
+                .take(MAX_RESULTS)
+                .map(|(idx, _, positions)| SymbolRow {
+                    glyph: glyph_for(idx),
+                    symbol: idx,
+                    positions,
+                })
+                .collect();
+        }
+
+        self.list.clamp(self.results.len());
+    }
+
+    /// Best of name and shortcode matches; only name matches carry highlight positions
+    fn score(matcher: &SkimMatcherV2, symbol: &Symbol, query: &str) -> Option<(i64, Vec<usize>)> {
+        let name = matcher.fuzzy_indices(&symbol.name, query);
+        let keywords = matcher.fuzzy_match(&symbol.keywords, query);
+        match (name, keywords) {
+            (Some((n, positions)), Some(k)) if n >= k => Some((n, positions)),
+            (_, Some(k)) => Some((k, Vec::new())),
+            (name, None) => name,
+        }
+    }
+
+    pub fn arrow_up(&mut self) {
+        if !self.variants.is_empty() {
+            match self.selected_variant {
+                Some(v) => {
+                    self.selected_variant = v.checked_sub(1);
+                    return;
+                }
+                None => self.variants.clear(),
+            }
+        }
+        self.list.up(self.results.len());
+    }
+
+    pub fn arrow_down(&mut self) {
+        if !self.variants.is_empty() {
+            let next = self.selected_variant.map_or(0, |v| v + 1);
+            if next < self.variants.len() {
+                self.selected_variant = Some(next);
+                return;
+            }
+            self.variants.clear();
+            self.selected_variant = None;
+        }
+        self.list.down(self.results.len());
+    }
+
+    /// Tab: show or hide the skin tone variants of the selected emoji
+    pub fn toggle_variants(&mut self) {
+        self.selected_variant = None;
+        if !self.variants.is_empty() {
+            self.variants.clear();
+            return;
+        }
+        if let Some(row) = self.results.get(self.list.selected()) {
+            self.variants = symbols::table()[row.symbol].skin_tone_variants();
+        }
+    }
+
+    /// Copy the selected glyph and remember it; true when something was copied
+    pub fn activate(&mut self) -> bool {
+        let glyph = match self.selected_variant.and_then(|v| self.variants.get(v)) {
+            Some(variant) => variant.clone(),
+            None => match self.results.get(self.list.selected()) {
+                Some(row) => row.glyph.clone(),
+                None => return false,
+            },
+        };
+
+        if let Err(e) = copy::copy_to_clipboard(&glyph) {
+            eprintln!("[Symbols] Failed to copy {:?}: {}", glyph, e);
+            return false;
+        }
+        symbols::record_use(&mut self.recent, &glyph);
+        true
+    }
+
+    pub fn view<'a>(&self, theme: &'a Theme, font: iced::Font, font_size: f32) -> Element<'a, Message> {
+        let small = font_size * 0.8;
+        let table = symbols::table();
+
+        if self.results.is_empty() {
+            return list_window::placeholder("No matching symbols", theme, font, font_size);
+        }
+
+        let mut items = column![].spacing(1);
+        for list_idx in self.list.visible(self.results.len()) {
+            let result = &self.results[list_idx];
+            let symbol = &table[result.symbol];
+            let expanded = list_idx == self.list.selected() && !self.variants.is_empty();
+            let selected = list_idx == self.list.selected() && self.selected_variant.is_none();
+            let fg = if selected { theme.background } else { theme.foreground };
+            let highlight = if selected { theme.color1 } else { theme.color3 };
+            let bg = if selected { Some(theme.color3.into()) } else { None };
+
+            let marker = match (selected, expanded) {
+                (true, _) => ">>",
+                (false, true) => " v",
+                (false, false) => "  ",
+            };
+
+            let mut name_row = row![].spacing(0);
+            for (segment, highlighted) in AppList::highlight_segments(&symbol.name, &result.positions) {
+                name_row = name_row.push(
+                    text(segment)
+                        .font(font)
+                        .size(font_size)
+                        .color(if highlighted { highlight } else { fg }),
+                );
+            }
+            if !symbol.keywords.is_empty() {
+                name_row = name_row.push(
+                    text(format!("  :{}:", symbol.keywords.split(' ').next().unwrap_or_default()))
+                        .font(font)
+                        .size(small)
+                        .color(if selected { fg } else { theme.color7 }),
+                );
+            }
+
+            let content = row![
+                text(marker).font(font).size(font_size).color(fg),
+                text(result.glyph.clone())
+                    .font(font)
+                    .size(font_size * 1.2)
+                    .width(Length::Fixed(font_size * 2.0)),
+                name_row,
+            ]
+            .spacing(4)
+            .align_y(iced::Alignment::Center);
+            items = items.push(list_window::row(content, bg));
+
+            if expanded {
+                for (variant_idx, variant) in self.variants.iter().enumerate() {
+                    let variant_selected = self.selected_variant == Some(variant_idx);
+                    let fg = if variant_selected { theme.background } else { theme.color7 };
+                    let bg = if variant_selected { Some(theme.color3.into()) } else { None };
+
+                    let content = row![
+                        text(if variant_selected { "  >>" } else { "    " })
+                            .font(font)
+                            .size(font_size)
+                            .color(fg),
+                        text(variant.clone()).font(font).size(font_size * 1.2),
+                    ]
+                    .spacing(4)
+                    .align_y(iced::Alignment::Center);
+                    items = items.push(list_window::row(content, bg));
+                }
+            }
+        }
+
+        items.into()
+    }
+}
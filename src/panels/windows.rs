@@ -0,0 +1,205 @@
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use iced::widget::{column, row, text};
+use iced::Element;
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use crate::panels::app_list::AppList;
+use crate::panels::list_window::{self, ListWindow};
+use crate::utils::compositor::{self, WindowBackend, WindowInfo};
+use crate::utils::normalize;
+use crate::utils::theme::Theme;
+use crate::Message;
+
+/// Lists open windows for `! query` and focuses the chosen one
+pub struct WindowPanel {
+    backend: Option<Arc<dyn WindowBackend>>,
+    windows: Vec<WindowInfo>,
+    /// Filled by the thread listing windows for the latest `refresh`, taken by `check_loaded`
+    listing: Arc<Mutex<Option<io::Result<Vec<WindowInfo>>>>>,
+    /// A listing is running and `windows` is empty until it arrives
+    loading: bool,
+    /// Last IPC failure, shown instead of the list
+    error: Option<String>,
+    query: String,
+    /// (index into `windows`, matched positions in the title)
+    results: Vec<(usize, Vec<usize>)>,
+    matcher: SkimMatcherV2,
+    list: ListWindow,
+}
+
+impl WindowPanel {
+    pub fn new() -> Self {
+        Self::with_backend(compositor::detect())
+    }
+
+    pub fn with_backend(backend: Option<Box<dyn WindowBackend>>) -> Self {
+        Self {
+            backend: backend.map(Arc::from),
+            windows: Vec::new(),
+            listing: Arc::new(Mutex::new(None)),
+            loading: false,
+            error: None,
+            query: String::new(),
+            results: Vec::new(),
+            matcher: SkimMatcherV2::default(),
+            list: ListWindow::new(),
+        }
+    }
+
+    /// Ask the compositor for its current windows on a background thread, so a hung IPC socket
+    /// can't stall the UI; call when entering window mode
+    pub fn refresh(&mut self) {
+        let Some(backend) = self.backend.clone() else {
+            self.error = Some("No supported compositor (Hyprland or Sway) detected".to_string());
+            return;
+        };
+
+        // A fresh slot, so a listing still running from before can't overwrite this one
+        let listing = Arc::new(Mutex::new(None));
+        self.listing = Arc::clone(&listing);
+        self.loading = true;
+        self.error = None;
+        self.windows.clear();
+        self.filter();
+
+        thread::spawn(move || {
+            let windows = backend.windows();
+            *listing.lock().unwrap() = Some(windows);
+        });
+    }
+
+    /// Pick up the windows once listed; call every frame while in window mode
+    pub fn check_loaded(&mut self) {
+        let Some(listed) = self.listing.lock().unwrap().take() else {
+            return;
+        };
+        let Some(backend) = &self.backend else {
+            return;
+        };
+        self.loading = false;
+
+        match listed {
+            Ok(windows) => {
+                self.windows = windows;
+                self.error = None;
+            }
+            Err(e) => {
+                eprintln!("[Windows] Failed to list {} windows: {}", backend.name(), e);
+                self.windows.clear();
+                self.error = Some(format!("{} IPC error: {}", backend.name(), e));
+            }
+        }
+        self.filter();
+    }
+
+    pub fn set_query(&mut self, query: &str) {
+        self.query = query.trim().to_string();
+        self.list.reset();
+        self.filter();
+    }
+
+    fn filter(&mut self) {
+        if self.query.is_empty() {
+            self.results = (0..self.windows.len()).map(|idx| (idx, Vec::new())).collect();
+        } else {
+            let query = normalize::fold(&self.query);
+            let matcher = &self.matcher;
+            let mut scored: Vec<(usize, i64, Vec<usize>)> = self
+                .windows
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, window)| {
+                    let title = matcher.fuzzy_indices(&normalize::fold(&window.title), &query);
+                    let class = matcher.fuzzy_match(&normalize::fold(&window.class), &query);
+                    match (title, class) {
+                        (Some((t, positions)), Some(c)) if t >= c => Some((idx, t, positions)),
+                        (_, Some(c)) => Some((idx, c, Vec::new())),
+                        (Some((t, positions)), None) => Some((idx, t, positions)),
+                        (None, None) => None,
+                    }
+                })
+                .collect();
+            // Stable sort keeps the compositor's focus order for equal scores
+            scored.sort_by_key(|s| std::cmp::Reverse(s.1));
+            self.results = scored.into_iter().map(|(idx, _, positions)| (idx, positions)).collect();
+        }
+
+        self.list.clamp(self.results.len());
+    }
+
+    pub fn arrow_up(&mut self) {
+        self.list.up(self.results.len());
+    }
+
+    pub fn arrow_down(&mut self) {
+        self.list.down(self.results.len());
+    }
+
+    /// Focus the selected window; true when the compositor accepted it
+    pub fn activate(&self) -> bool {
+        let (Some(backend), Some((idx, _))) = (&self.backend, self.results.get(self.list.selected())) else {
+            return false;
+        };
+        let window = &self.windows[*idx];
+
+        match backend.focus(window) {
+            Ok(()) => true,
+            Err(e) => {
+                eprintln!("[Windows] Failed to focus {:?}: {}", window.title, e);
+                false
+            }
+        }
+    }
+
+    pub fn view<'a>(&self, theme: &'a Theme, font: iced::Font, font_size: f32) -> Element<'a, Message> {
+        let small = font_size * 0.8;
+
+        let message = match &self.error {
+            Some(error) => Some(error.clone()),
+            None if self.loading => Some("Listing windows...".to_string()),
+            None if self.results.is_empty() && self.query.is_empty() => Some("No open windows".to_string()),
+            None if self.results.is_empty() => Some("No matching windows".to_string()),
+            None => None,
+        };
+        if let Some(message) = message {
+            return list_window::placeholder(message, theme, font, font_size);
+        }
+
+        let mut items = column![].spacing(1);
+        for list_idx in self.list.visible(self.results.len()) {
+            let (window_idx, positions) = &self.results[list_idx];
+            let window = &self.windows[*window_idx];
+            let selected = list_idx == self.list.selected();
+            let fg = if selected { theme.background } else { theme.foreground };
+            let highlight = if selected { theme.color1 } else { theme.color3 };
+            let bg = if selected { Some(theme.color3.into()) } else { None };
+            let dim = if selected { fg } else { theme.color7 };
+
+            let mut title_row = row![].spacing(0);
+            for (segment, highlighted) in AppList::highlight_segments(&window.title, positions) {
+                title_row = title_row.push(
+                    text(segment)
+                        .font(font)
+                        .size(font_size)
+                        .color(if highlighted { highlight } else { fg }),
+                );
+            }
+
+            let content = row![
+                text(if selected { ">>" } else { "  " }).font(font).size(font_size).color(fg),
+                text(format!("[{}]", window.workspace)).font(font).size(small).color(dim),
+                title_row,
+                text(window.class.clone()).font(font).size(small).color(dim),
+            ]
+            .spacing(6)
+            .align_y(iced::Alignment::Center);
+
+            items = items.push(list_window::row(content, bg));
+        }
+
+        items.into()
+    }
+}
@@ -0,0 +1,472 @@
+//! Expression evaluation for the `=` calculator mode, plus its persisted history.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs;
+use std::path::PathBuf;
+
+const HISTORY_FILE: &str = ".cache/sierra/calculator.cache";
+const MAX_HISTORY: usize = 20;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalcEntry {
+    pub expression: String,
+    pub result: String,
+}
+
+/// Evaluated expression, formatted for display and copying
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalcResult {
+    pub value: String,
+    /// Hex/binary/octal rendering, shown when the input used such literals
+    pub alternate: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Radix {
+    Hex,
+    Bin,
+    Oct,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Op(char),
+    LParen,
+    RParen,
+    Comma,
+}
+
+/// Evaluate `expression`, e.g. `2*(3+4)/7`, `0x1f + 12`, `sqrt(2) * pi`
+pub fn evaluate(expression: &str) -> Result<CalcResult, String> {
+    let (tokens, radix) = tokenize(expression)?;
+    if tokens.is_empty() {
+        return Err("Empty expression".to_string());
+    }
+
+    let mut parser = Parser { tokens, pos: 0 };
+    let value = parser.expression()?;
+    if parser.pos < parser.tokens.len() {
+        return Err(format!("Unexpected {:?}", parser.tokens[parser.pos]));
+    }
+    if !value.is_finite() {
+        return Err("Result is not a finite number".to_string());
+    }
+
+    Ok(CalcResult {
+        value: format_number(value),
+        alternate: radix.and_then(|r| format_radix(value, r)),
+    })
+}
+
+fn tokenize(input: &str) -> Result<(Vec<Token>, Option<Radix>), String> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut radix = None;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '0' if matches!(chars.get(i + 1), Some('x' | 'X' | 'b' | 'B' | 'o' | 'O')) => {
+                let (base, r) = match chars[i + 1].to_ascii_lowercase() {
+                    'x' => (16, Radix::Hex),
+                    'b' => (2, Radix::Bin),
+                    _ => (8, Radix::Oct),
+                };
+                let start = i + 2;
+                let mut end = start;
+                while end < chars.len() && (chars[end].is_digit(base) || chars[end] == '_') {
+                    end += 1;
+                }
+                let digits: String = chars[start..end].iter().filter(|c| **c != '_').collect();
+                let value = u64::from_str_radix(&digits, base)
+                    .map_err(|_| format!("Invalid number 0{}{}", chars[i + 1], digits))?;
+                tokens.push(Token::Number(value as f64));
+                radix.get_or_insert(r);
+                i = end;
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.' || chars[i] == '_') {
+                    i += 1;
+                }
+                // Scientific notation: 1e3, 2.5E-4
+                if i < chars.len() && matches!(chars[i], 'e' | 'E') {
+                    let mut j = i + 1;
+                    if j < chars.len() && matches!(chars[j], '+' | '-') {
+                        j += 1;
+                    }
+                    if j < chars.len() && chars[j].is_ascii_digit() {
+                        i = j;
+                        while i < chars.len() && chars[i].is_ascii_digit() {
+                            i += 1;
+                        }
+                    }
+                }
+                let literal: String = chars[start..i].iter().filter(|c| **c != '_').collect();
+                let value = literal
+                    .parse::<f64>()
+                    .map_err(|_| format!("Invalid number {}", literal))?;
+                tokens.push(Token::Number(value));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                tokens.push(Token::Ident(chars[start..i].iter().collect::<String>().to_lowercase()));
+            }
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                tokens.push(Token::Op('^'));
+                i += 2;
+            }
+            '+' | '-' | '*' | '/' | '%' | '^' => {
+                tokens.push(Token::Op(c));
+                i += 1;
+            }
+            '×' => {
+                tokens.push(Token::Op('*'));
+                i += 1;
+            }
+            '÷' => {
+                tokens.push(Token::Op('/'));
+                i += 1;
+            }
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            ',' => {
+                tokens.push(Token::Comma);
+                i += 1;
+            }
+            _ => return Err(format!("Unexpected character '{}'", c)),
+        }
+    }
+
+    Ok((tokens, radix))
+}
+
+/// Recursive descent over:
+///   expression = term (('+' | '-') term)*
+///   term       = unary (('*' | '/' | '%') unary | implicit-multiplication)*
+///   unary      = '-' unary | '+' unary | power
+///   power      = primary ('^' unary)?
+///   primary    = number | constant | function '(' args ')' | '(' expression ')'
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expression(&mut self) -> Result<f64, String> {
+        let mut value = self.term()?;
+        while let Some(Token::Op(op @ ('+' | '-'))) = self.peek().cloned() {
+            self.pos += 1;
+            let rhs = self.term()?;
+            value = if op == '+' { value + rhs } else { value - rhs };
+        }
+        Ok(value)
+    }
+
+    fn term(&mut self) -> Result<f64, String> {
+        let mut value = self.unary()?;
+        loop {
+            match self.peek().cloned() {
+                Some(Token::Op(op @ ('*' | '/' | '%'))) => {
+                    self.pos += 1;
+                    let rhs = self.unary()?;
+                    value = match op {
+                        '*' => value * rhs,
+                        '/' if rhs == 0.0 => return Err("Division by zero".to_string()),
+                        '/' => value / rhs,
+                        _ => value % rhs,
+                    };
+                }
+                // 2pi, 3(4+5), 2sqrt(2)
+                Some(Token::LParen | Token::Ident(_) | Token::Number(_)) => {
+                    value *= self.power()?;
+                }
+                _ => return Ok(value),
+            }
+        }
+    }
+
+    fn unary(&mut self) -> Result<f64, String> {
+        match self.peek() {
+            Some(Token::Op('-')) => {
+                self.pos += 1;
+                Ok(-self.unary()?)
+            }
+            Some(Token::Op('+')) => {
+                self.pos += 1;
+                self.unary()
+            }
+            _ => self.power(),
+        }
+    }
+
+    fn power(&mut self) -> Result<f64, String> {
+        let base = self.primary()?;
+        if let Some(Token::Op('^')) = self.peek() {
+            self.pos += 1;
+            // Right associative: 2^3^2 = 2^9
+            let exponent = self.unary()?;
+            return Ok(base.powf(exponent));
+        }
+        Ok(base)
+    }
+
+    fn primary(&mut self) -> Result<f64, String> {
+        match self.next() {
+            Some(Token::Number(n)) => Ok(n),
+            Some(Token::LParen) => {
+                let value = self.expression()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(value),
+                    _ => Err("Missing ')'".to_string()),
+                }
+            }
+            Some(Token::Ident(name)) => {
+                if let Some(Token::LParen) = self.peek() {
+                    self.pos += 1;
+                    let args = self.arguments()?;
+                    return call(&name, &args);
+                }
+                constant(&name).ok_or_else(|| format!("Unknown name '{}'", name))
+            }
+            Some(token) => Err(format!("Unexpected {:?}", token)),
+            None => Err("Incomplete expression".to_string()),
+        }
+    }
+
+    fn arguments(&mut self) -> Result<Vec<f64>, String> {
+        let mut args = Vec::new();
+        if let Some(Token::RParen) = self.peek() {
+            self.pos += 1;
+            return Ok(args);
+        }
+        loop {
+            args.push(self.expression()?);
+            match self.next() {
+                Some(Token::Comma) => continue,
+                Some(Token::RParen) => return Ok(args),
+                _ => return Err("Missing ')'".to_string()),
+            }
+        }
+    }
+}
+
+fn constant(name: &str) -> Option<f64> {
+    match name {
+        "pi" | "π" => Some(std::f64::consts::PI),
+        "e" => Some(std::f64::consts::E),
+        "tau" | "τ" => Some(std::f64::consts::TAU),
+        "phi" | "φ" => Some(1.618_033_988_749_895),
+        _ => None,
+    }
+}
+
+fn call(name: &str, args: &[f64]) -> Result<f64, String> {
+    let one = |f: fn(f64) -> f64| match args {
+        [x] => Ok(f(*x)),
+        _ => Err(format!("{}() takes 1 argument", name)),
+    };
+
+    match name {
+        "sqrt" => one(f64::sqrt),
+        "cbrt" => one(f64::cbrt),
+        "abs" => one(f64::abs),
+        "floor" => one(f64::floor),
+        "ceil" => one(f64::ceil),
+        "round" => one(f64::round),
+        "trunc" => one(f64::trunc),
+        "exp" => one(f64::exp),
+        "ln" => one(f64::ln),
+        "log" | "log10" => one(f64::log10),
+        "log2" => one(f64::log2),
+        "sin" => one(f64::sin),
+        "cos" => one(f64::cos),
+        "tan" => one(f64::tan),
+        "asin" => one(f64::asin),
+        "acos" => one(f64::acos),
+        "atan" => one(f64::atan),
+        "sinh" => one(f64::sinh),
+        "cosh" => one(f64::cosh),
+        "tanh" => one(f64::tanh),
+        "rad" => one(f64::to_radians),
+        "deg" => one(f64::to_degrees),
+        "pow" => match args {
+            [b, e] => Ok(b.powf(*e)),
+            _ => Err("pow() takes 2 arguments".to_string()),
+        },
+        "min" if !args.is_empty() => Ok(args.iter().copied().fold(f64::INFINITY, f64::min)),
+        "max" if !args.is_empty() => Ok(args.iter().copied().fold(f64::NEG_INFINITY, f64::max)),
+        _ => Err(format!("Unknown function '{}'", name)),
+    }
+}
+
+/// Integers without a fraction, everything else with up to 10 significant decimals
+fn format_number(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        return format!("{}", value as i64);
+    }
+    if value.abs() >= 1e15 || value.abs() < 1e-6 {
+        return format!("{:e}", value);
+    }
+
+    let formatted = format!("{:.10}", value);
+    formatted.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+fn format_radix(value: f64, radix: Radix) -> Option<String> {
+    if value.fract() != 0.0 || value.abs() >= 1e15 {
+        return None;
+    }
+    let int = value as i64;
+    let sign = if int < 0 { "-" } else { "" };
+    let abs = int.unsigned_abs();
+    Some(match radix {
+        Radix::Hex => format!("{}0x{:x}", sign, abs),
+        Radix::Bin => format!("{}0b{:b}", sign, abs),
+        Radix::Oct => format!("{}0o{:o}", sign, abs),
+    })
+}
+
+fn get_history_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(HISTORY_FILE)
+}
+
+/// Recent calculations, newest first
+pub fn load_history() -> VecDeque<CalcEntry> {
+    if let Ok(content) = fs::read(get_history_path()) {
+        if let Ok(history) = bincode::deserialize::<VecDeque<CalcEntry>>(&content) {
+            return history;
+        }
+    }
+    VecDeque::new()
+}
+
+pub fn save_history(history: &VecDeque<CalcEntry>) {
+    let path = get_history_path();
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Ok(encoded) = bincode::serialize(history) {
+        if let Err(e) = fs::write(&path, encoded) {
+            eprintln!("Failed to save calculator history: {}", e);
+        }
+    }
+}
+
+/// Put `entry` on top of `history`, dropping an older identical calculation
+pub fn push_history(history: &mut VecDeque<CalcEntry>, entry: CalcEntry) {
+    history.retain(|e| e.expression != entry.expression);
+    history.push_front(entry);
+    history.truncate(MAX_HISTORY);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value(expression: &str) -> String {
+        evaluate(expression).unwrap().value
+    }
+
+    fn error(expression: &str) -> String {
+        evaluate(expression).unwrap_err()
+    }
+
+    #[test]
+    fn precedence_and_associativity() {
+        assert_eq!(value("2 + 3 * 4"), "14");
+        assert_eq!(value("(2 + 3) * 4"), "20");
+        assert_eq!(value("10 - 4 - 3"), "3");
+        assert_eq!(value("10 / 4"), "2.5");
+        assert_eq!(value("10 % 3 + 1"), "2");
+        assert_eq!(value("2 ^ 3 ^ 2"), "512");
+        assert_eq!(value("2 ** 10"), "1024");
+        assert_eq!(value("6 × 7 ÷ 2"), "21");
+        assert_eq!(value("1e3 + 2.5E-1"), "1000.25");
+        assert_eq!(value("1_000 * 3"), "3000");
+    }
+
+    #[test]
+    fn unary_minus() {
+        assert_eq!(value("-3 + 5"), "2");
+        assert_eq!(value("-2 ^ 2"), "-4");
+        assert_eq!(value("2 ^ -1"), "0.5");
+        assert_eq!(value("4 * -(1 + 1)"), "-8");
+        assert_eq!(value("--3"), "3");
+    }
+
+    #[test]
+    fn implicit_multiplication() {
+        assert_eq!(value("2(3 + 4)"), "14");
+        assert_eq!(value("(1 + 1)(2 + 2)"), "8");
+        assert_eq!(value("2pi"), value("2 * pi"));
+        assert_eq!(value("3sqrt(16)"), "12");
+    }
+
+    #[test]
+    fn radix_literals_keep_their_base() {
+        let hex = evaluate("0x1f + 1").unwrap();
+        assert_eq!(hex.value, "32");
+        assert_eq!(hex.alternate.as_deref(), Some("0x20"));
+
+        assert_eq!(evaluate("0b101").unwrap().alternate.as_deref(), Some("0b101"));
+        assert_eq!(evaluate("0o17 - 20").unwrap().alternate.as_deref(), Some("-0o5"));
+        assert_eq!(evaluate("0xff / 2").unwrap().alternate, None);
+        assert_eq!(evaluate("12").unwrap().alternate, None);
+        assert!(error("0xg").starts_with("Invalid number"));
+    }
+
+    #[test]
+    fn functions_and_constants() {
+        assert_eq!(value("sqrt(2) ^ 2"), "2");
+        assert_eq!(value("max(3, 9, 4) - min(3, 9, 4)"), "6");
+        assert_eq!(value("pow(2, 8)"), "256");
+        assert_eq!(value("round(2.5) + floor(-0.5)"), "2");
+        assert_eq!(value("deg(pi)"), "180");
+        assert_eq!(value("PI"), value("π"));
+        assert_eq!(value("ln(e)"), "1");
+        assert_eq!(value("log(1000)"), "3");
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(error("1 / 0"), "Division by zero");
+        assert_eq!(error("1 / (2 - 2)"), "Division by zero");
+        assert_eq!(error("(1 + 2"), "Missing ')'");
+        assert_eq!(error("max(1, 2"), "Missing ')'");
+        assert_eq!(error("1 + 2)"), "Unexpected RParen");
+        assert_eq!(error("foo + 1"), "Unknown name 'foo'");
+        assert_eq!(error("foo(1)"), "Unknown function 'foo'");
+        assert_eq!(error("sqrt(1, 2)"), "sqrt() takes 1 argument");
+        assert_eq!(error("2 +"), "Incomplete expression");
+        assert_eq!(error("   "), "Empty expression");
+        assert_eq!(error("2 $ 3"), "Unexpected character '$'");
+        assert_eq!(error("sqrt(-1)"), "Result is not a finite number");
+    }
+}
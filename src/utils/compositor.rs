@@ -0,0 +1,360 @@
+//! Window listing and focusing over the compositor's IPC socket (Hyprland or Sway).
+
+use serde_json::Value;
+use std::io::{self, Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Compositors answer immediately; anything slower means the socket is stale
+const IPC_TIMEOUT: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowInfo {
+    /// Backend-specific handle: a Hyprland address or a Sway container id
+    pub id: String,
+    pub title: String,
+    /// Window class / Wayland app_id
+    pub class: String,
+    pub workspace: String,
+}
+
+/// A compositor that can list its windows and focus one of them
+pub trait WindowBackend: Send + Sync {
+    fn name(&self) -> &'static str;
+    /// Open windows, most relevant first
+    fn windows(&self) -> io::Result<Vec<WindowInfo>>;
+    fn focus(&self, window: &WindowInfo) -> io::Result<()>;
+}
+
+/// Backend for the running compositor, picked from its environment variables
+pub fn detect() -> Option<Box<dyn WindowBackend>> {
+    if let Some(socket) = HyprlandIpc::socket_from_env() {
+        return Some(Box::new(HyprlandIpc::new(socket)));
+    }
+    if let Some(socket) = std::env::var_os("SWAYSOCK") {
+        return Some(Box::new(SwayIpc::new(PathBuf::from(socket))));
+    }
+    None
+}
+
+fn connect(socket: &Path) -> io::Result<UnixStream> {
+    let stream = UnixStream::connect(socket)?;
+    stream.set_read_timeout(Some(IPC_TIMEOUT))?;
+    stream.set_write_timeout(Some(IPC_TIMEOUT))?;
+    Ok(stream)
+}
+
+fn invalid_data(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+/// Hyprland's request socket: one plain-text request per connection, answered then closed
+pub struct HyprlandIpc {
+    socket: PathBuf,
+}
+
+impl HyprlandIpc {
+    pub fn new(socket: PathBuf) -> Self {
+        Self { socket }
+    }
+
+    /// `$XDG_RUNTIME_DIR/hypr/<signature>/.socket.sock`, or `/tmp/hypr/...` on older releases
+    fn socket_from_env() -> Option<PathBuf> {
+        let signature = std::env::var("HYPRLAND_INSTANCE_SIGNATURE").ok()?;
+        let runtime = std::env::var_os("XDG_RUNTIME_DIR").map(PathBuf::from);
+        runtime
+            .into_iter()
+            .chain(std::iter::once(PathBuf::from("/tmp")))
+            .map(|dir| dir.join("hypr").join(&signature).join(".socket.sock"))
+            .find(|path| path.exists())
+    }
+
+    fn request(&self, request: &str) -> io::Result<String> {
+        let mut stream = connect(&self.socket)?;
+        stream.write_all(request.as_bytes())?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+        Ok(response)
+    }
+}
+
+impl WindowBackend for HyprlandIpc {
+    fn name(&self) -> &'static str {
+        "Hyprland"
+    }
+
+    fn windows(&self) -> io::Result<Vec<WindowInfo>> {
+        let response = self.request("j/clients")?;
+        let clients: Vec<Value> =
+            serde_json::from_str(&response).map_err(|e| invalid_data(e.to_string()))?;
+
+        let mut windows: Vec<(i64, WindowInfo)> = clients
+            .iter()
+            .filter(|c| c["mapped"].as_bool().unwrap_or(true) && !c["hidden"].as_bool().unwrap_or(false))
+            .filter_map(|c| {
+                let window = WindowInfo {
+                    id: c["address"].as_str()?.to_string(),
+                    title: c["title"].as_str().unwrap_or_default().to_string(),
+                    class: c["class"].as_str().unwrap_or_default().to_string(),
+                    workspace: c["workspace"]["name"].as_str().unwrap_or_default().to_string(),
+                };
+                Some((c["focusHistoryID"].as_i64().unwrap_or(i64::MAX), window))
+            })
+            .collect();
+
+        // Most recently focused first, like alt-tab
+        windows.sort_by_key(|(history, _)| *history);
+        Ok(windows.into_iter().map(|(_, w)| w).collect())
+    }
+
+    fn focus(&self, window: &WindowInfo) -> io::Result<()> {
+        let response = self.request(&format!("dispatch focuswindow address:{}", window.id))?;
+        if response.trim() == "ok" {
+            Ok(())
+        } else {
+            Err(io::Error::other(response.trim().to_string()))
+        }
+    }
+}
+
+/// Sway's i3-compatible IPC: `i3-ipc` magic, then payload length and message type (native endian)
+pub struct SwayIpc {
+    socket: PathBuf,
+}
+
+impl SwayIpc {
+    const MAGIC: &'static [u8; 6] = b"i3-ipc";
+    const RUN_COMMAND: u32 = 0;
+    const GET_TREE: u32 = 4;
+
+    pub fn new(socket: PathBuf) -> Self {
+        Self { socket }
+    }
+
+    fn request(&self, message_type: u32, payload: &str) -> io::Result<Value> {
+        let mut stream = connect(&self.socket)?;
+
+        let mut message = Vec::with_capacity(14 + payload.len());
+        message.extend_from_slice(Self::MAGIC);
+        message.extend_from_slice(&(payload.len() as u32).to_ne_bytes());
+        message.extend_from_slice(&message_type.to_ne_bytes());
+        message.extend_from_slice(payload.as_bytes());
+        stream.write_all(&message)?;
+
+        let mut header = [0u8; 14];
+        stream.read_exact(&mut header)?;
+        if &header[..6] != Self::MAGIC {
+            return Err(invalid_data("bad i3-ipc magic"));
+        }
+        let length = u32::from_ne_bytes([header[6], header[7], header[8], header[9]]) as usize;
+        let mut body = vec![0u8; length];
+        stream.read_exact(&mut body)?;
+
+        serde_json::from_slice(&body).map_err(|e| invalid_data(e.to_string()))
+    }
+
+    /// Leaf containers with a client, remembering the workspace they sit on
+    fn collect_windows(node: &Value, workspace: &str, windows: &mut Vec<WindowInfo>) {
+        let workspace = if node["type"] == "workspace" {
+            node["name"].as_str().unwrap_or(workspace)
+        } else {
+            workspace
+        };
+
+        let has_client = node["pid"].is_i64() || node["window"].is_i64();
+        if has_client && matches!(node["type"].as_str(), Some("con" | "floating_con")) {
+            // Native Wayland clients set app_id, Xwayland ones the X11 class
+            let class = node["app_id"]
+                .as_str()
+                .or_else(|| node["window_properties"]["class"].as_str())
+                .unwrap_or_default();
+            if let Some(id) = node["id"].as_i64() {
+                windows.push(WindowInfo {
+                    id: id.to_string(),
+                    title: node["name"].as_str().unwrap_or_default().to_string(),
+                    class: class.to_string(),
+                    workspace: workspace.to_string(),
+                });
+            }
+        }
+
+        for key in ["nodes", "floating_nodes"] {
+            if let Some(children) = node[key].as_array() {
+                for child in children {
+                    Self::collect_windows(child, workspace, windows);
+                }
+            }
+        }
+    }
+}
+
+impl WindowBackend for SwayIpc {
+    fn name(&self) -> &'static str {
+        "Sway"
+    }
+
+    fn windows(&self) -> io::Result<Vec<WindowInfo>> {
+        let tree = self.request(Self::GET_TREE, "")?;
+        let mut windows = Vec::new();
+        Self::collect_windows(&tree, "", &mut windows);
+        Ok(windows)
+    }
+
+    fn focus(&self, window: &WindowInfo) -> io::Result<()> {
+        let reply = self.request(Self::RUN_COMMAND, &format!("[con_id={}] focus", window.id))?;
+        let success = reply
+            .as_array()
+            .is_some_and(|results| results.iter().all(|r| r["success"].as_bool() == Some(true)));
+        if success {
+            Ok(())
+        } else {
+            Err(io::Error::other(reply.to_string()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::os::unix::net::UnixListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// A socket path in a fresh directory under the system temp dir
+    fn socket_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("sierra-compositor-{}-{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.join("ipc.sock")
+    }
+
+    /// Answer one Hyprland request per reply, sending back what each request was
+    fn serve_hyprland(socket: &Path, replies: Vec<String>) -> mpsc::Receiver<String> {
+        let listener = UnixListener::bind(socket).unwrap();
+        let (sender, requests) = mpsc::channel();
+        thread::spawn(move || {
+            for reply in replies {
+                let (mut stream, _) = listener.accept().unwrap();
+                // The client doesn't shut down its end, so one read is the whole request
+                let mut request = [0u8; 1024];
+                let n = stream.read(&mut request).unwrap();
+                sender.send(String::from_utf8_lossy(&request[..n]).into_owned()).unwrap();
+                stream.write_all(reply.as_bytes()).unwrap();
+            }
+        });
+        requests
+    }
+
+    /// Answer one i3-ipc message per reply, sending back its type and payload
+    fn serve_sway(socket: &Path, replies: Vec<Value>) -> mpsc::Receiver<(u32, String)> {
+        let listener = UnixListener::bind(socket).unwrap();
+        let (sender, requests) = mpsc::channel();
+        thread::spawn(move || {
+            for reply in replies {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut header = [0u8; 14];
+                stream.read_exact(&mut header).unwrap();
+                assert_eq!(&header[..6], SwayIpc::MAGIC);
+                let length = u32::from_ne_bytes(header[6..10].try_into().unwrap()) as usize;
+                let message_type = u32::from_ne_bytes(header[10..14].try_into().unwrap());
+                let mut payload = vec![0u8; length];
+                stream.read_exact(&mut payload).unwrap();
+                sender.send((message_type, String::from_utf8(payload).unwrap())).unwrap();
+
+                let body = reply.to_string();
+                let mut message = SwayIpc::MAGIC.to_vec();
+                message.extend_from_slice(&(body.len() as u32).to_ne_bytes());
+                message.extend_from_slice(&message_type.to_ne_bytes());
+                message.extend_from_slice(body.as_bytes());
+                stream.write_all(&message).unwrap();
+            }
+        });
+        requests
+    }
+
+    fn window(id: &str, title: &str, class: &str, workspace: &str) -> WindowInfo {
+        WindowInfo {
+            id: id.to_string(),
+            title: title.to_string(),
+            class: class.to_string(),
+            workspace: workspace.to_string(),
+        }
+    }
+
+    #[test]
+    fn hyprland_lists_mapped_clients_by_focus_history() {
+        let socket = socket_path("hyprland-list");
+        let clients = json!([
+            { "address": "0x1", "title": "Editor", "class": "code", "workspace": { "name": "2" }, "focusHistoryID": 1 },
+            { "address": "0x2", "title": "Hidden", "class": "x", "workspace": { "name": "1" }, "hidden": true },
+            { "address": "0x3", "title": "Term", "class": "foot", "workspace": { "name": "1" }, "focusHistoryID": 0 },
+            { "address": "0x4", "title": "Unmapped", "class": "x", "workspace": { "name": "1" }, "mapped": false },
+        ]);
+        let requests = serve_hyprland(&socket, vec![clients.to_string()]);
+
+        let windows = HyprlandIpc::new(socket).windows().unwrap();
+        assert_eq!(requests.recv().unwrap(), "j/clients");
+        assert_eq!(
+            windows,
+            vec![window("0x3", "Term", "foot", "1"), window("0x1", "Editor", "code", "2")]
+        );
+    }
+
+    #[test]
+    fn hyprland_focus_dispatches_by_address() {
+        let socket = socket_path("hyprland-focus");
+        let requests = serve_hyprland(&socket, vec!["ok".to_string(), "No such window".to_string()]);
+        let backend = HyprlandIpc::new(socket);
+        let target = window("0xabc", "Term", "foot", "1");
+
+        backend.focus(&target).unwrap();
+        assert_eq!(requests.recv().unwrap(), "dispatch focuswindow address:0xabc");
+        assert!(backend.focus(&target).is_err());
+    }
+
+    #[test]
+    fn sway_lists_windows_from_the_tree() {
+        let socket = socket_path("sway-list");
+        let tree = json!({
+            "type": "root",
+            "nodes": [{
+                "type": "output",
+                "nodes": [{
+                    "type": "workspace",
+                    "name": "3",
+                    "nodes": [
+                        { "type": "con", "id": 11, "pid": 100, "name": "Term", "app_id": "foot" },
+                        { "type": "con", "id": 12, "nodes": [] },
+                    ],
+                    "floating_nodes": [
+                        { "type": "floating_con", "id": 13, "window": 4, "name": "Game",
+                          "window_properties": { "class": "Steam" } },
+                    ],
+                }],
+            }],
+        });
+        let requests = serve_sway(&socket, vec![tree]);
+
+        let windows = SwayIpc::new(socket).windows().unwrap();
+        assert_eq!(requests.recv().unwrap(), (SwayIpc::GET_TREE, String::new()));
+        assert_eq!(
+            windows,
+            vec![window("11", "Term", "foot", "3"), window("13", "Game", "Steam", "3")]
+        );
+    }
+
+    #[test]
+    fn sway_focus_runs_a_con_id_command() {
+        let socket = socket_path("sway-focus");
+        let replies = vec![json!([{ "success": true }]), json!([{ "success": false }])];
+        let requests = serve_sway(&socket, replies);
+        let backend = SwayIpc::new(socket);
+        let target = window("42", "Term", "foot", "1");
+
+        backend.focus(&target).unwrap();
+        assert_eq!(requests.recv().unwrap(), (SwayIpc::RUN_COMMAND, "[con_id=42] focus".to_string()));
+        assert!(backend.focus(&target).is_err());
+    }
+}
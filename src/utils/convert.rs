@@ -0,0 +1,370 @@
+//! Offline unit and time zone conversions, e.g. `5 mi to km`, `72 f in c`, `15:00 UTC in Asia/Tokyo`.
+
+use chrono::{DateTime, Local, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+/// A successful conversion: `display` is shown in the result list, `value` is what Enter copies
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conversion {
+    pub display: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Dimension {
+    Length,
+    Mass,
+    Volume,
+    Area,
+    Time,
+    Speed,
+    Data,
+    Temperature,
+}
+
+/// (aliases, dimension, factor to the dimension's base unit); the first alias is the display label.
+/// Matched case-insensitively, except data size symbols (see `alias_matches`).
+const UNITS: &[(&[&str], Dimension, f64)] = &[
+    // Length, base: metre
+    (&["nm", "nanometer", "nanometers", "nanometre", "nanometres"], Dimension::Length, 1e-9),
+    (&["um", "µm", "micrometer", "micrometers", "micron", "microns"], Dimension::Length, 1e-6),
+    (&["mm", "millimeter", "millimeters", "millimetre", "millimetres"], Dimension::Length, 1e-3),
+    (&["cm", "centimeter", "centimeters", "centimetre", "centimetres"], Dimension::Length, 1e-2),
+    (&["m", "meter", "meters", "metre", "metres"], Dimension::Length, 1.0),
+    (&["km", "kilometer", "kilometers", "kilometre", "kilometres"], Dimension::Length, 1e3),
+    (&["in", "inch", "inches", "\""], Dimension::Length, 0.0254),
+    (&["ft", "foot", "feet", "'"], Dimension::Length, 0.3048),
+    (&["yd", "yard", "yards"], Dimension::Length, 0.9144),
+    (&["mi", "mile", "miles"], Dimension::Length, 1609.344),
+    (&["nmi", "nautical mile", "nautical miles"], Dimension::Length, 1852.0),
+    // Mass, base: kilogram
+    (&["mg", "milligram", "milligrams"], Dimension::Mass, 1e-6),
+    (&["g", "gram", "grams"], Dimension::Mass, 1e-3),
+    (&["kg", "kilogram", "kilograms", "kilo", "kilos"], Dimension::Mass, 1.0),
+    (&["t", "tonne", "tonnes", "metric ton"], Dimension::Mass, 1e3),
+    (&["oz", "ounce", "ounces"], Dimension::Mass, 0.028_349_523_125),
+    (&["lb", "lbs", "pound", "pounds"], Dimension::Mass, 0.453_592_37),
+    (&["st", "stone", "stones"], Dimension::Mass, 6.350_293_18),
+    // Volume, base: litre
+    (&["ml", "milliliter", "milliliters", "millilitre", "millilitres"], Dimension::Volume, 1e-3),
+    (&["cl", "centiliter", "centiliters", "centilitre", "centilitres"], Dimension::Volume, 1e-2),
+    (&["l", "liter", "liters", "litre", "litres"], Dimension::Volume, 1.0),
+    (&["m3", "m³", "cubic meter", "cubic meters"], Dimension::Volume, 1e3),
+    (&["tsp", "teaspoon", "teaspoons"], Dimension::Volume, 0.004_928_921_593_75),
+    (&["tbsp", "tablespoon", "tablespoons"], Dimension::Volume, 0.014_786_764_781_25),
+    (&["floz", "fl oz", "fluid ounce", "fluid ounces"], Dimension::Volume, 0.029_573_529_562_5),
+    (&["cup", "cups"], Dimension::Volume, 0.236_588_236_5),
+    (&["pt", "pint", "pints"], Dimension::Volume, 0.473_176_473),
+    (&["qt", "quart", "quarts"], Dimension::Volume, 0.946_352_946),
+    (&["gal", "gallon", "gallons"], Dimension::Volume, 3.785_411_784),
+    // Area, base: square metre
+    (&["cm2", "cm²"], Dimension::Area, 1e-4),
+    (&["m2", "m²", "sqm"], Dimension::Area, 1.0),
+    (&["km2", "km²"], Dimension::Area, 1e6),
+    (&["ha", "hectare", "hectares"], Dimension::Area, 1e4),
+    (&["ft2", "ft²", "sqft"], Dimension::Area, 0.092_903_04),
+    (&["acre", "acres"], Dimension::Area, 4_046.856_422_4),
+    (&["mi2", "mi²"], Dimension::Area, 2_589_988.110_336),
+    // Time, base: second
+    (&["ms", "millisecond", "milliseconds"], Dimension::Time, 1e-3),
+    (&["s", "sec", "secs", "second", "seconds"], Dimension::Time, 1.0),
+    (&["min", "mins", "minute", "minutes"], Dimension::Time, 60.0),
+    (&["h", "hr", "hrs", "hour", "hours"], Dimension::Time, 3600.0),
+    (&["d", "day", "days"], Dimension::Time, 86_400.0),
+    (&["wk", "week", "weeks"], Dimension::Time, 604_800.0),
+    (&["yr", "year", "years"], Dimension::Time, 31_557_600.0),
+    // Speed, base: metre per second
+    (&["m/s", "mps"], Dimension::Speed, 1.0),
+    (&["km/h", "kmh", "kph"], Dimension::Speed, 1.0 / 3.6),
+    (&["mph", "mi/h"], Dimension::Speed, 0.447_04),
+    (&["kn", "kt", "knot", "knots"], Dimension::Speed, 1852.0 / 3600.0),
+    (&["ft/s", "fps"], Dimension::Speed, 0.3048),
+    // Data, base: byte
+    (&["bit", "bits", "b"], Dimension::Data, 0.125),
+    (&["B", "byte", "bytes"], Dimension::Data, 1.0),
+    (&["kB", "KB", "kilobyte", "kilobytes"], Dimension::Data, 1e3),
+    (&["MB", "megabyte", "megabytes"], Dimension::Data, 1e6),
+    (&["GB", "gigabyte", "gigabytes"], Dimension::Data, 1e9),
+    (&["TB", "terabyte", "terabytes"], Dimension::Data, 1e12),
+    (&["PB", "petabyte", "petabytes"], Dimension::Data, 1e15),
+    (&["KiB", "kibibyte", "kibibytes"], Dimension::Data, 1024.0),
+    (&["MiB", "mebibyte", "mebibytes"], Dimension::Data, 1_048_576.0),
+    (&["GiB", "gibibyte", "gibibytes"], Dimension::Data, 1_073_741_824.0),
+    (&["TiB", "tebibyte", "tebibytes"], Dimension::Data, 1_099_511_627_776.0),
+    (&["kbit", "kb", "Kb", "kbps", "kilobit", "kilobits"], Dimension::Data, 125.0),
+    (&["Mbit", "Mb", "mbps", "megabit", "megabits"], Dimension::Data, 125_000.0),
+    (&["Gbit", "Gb", "gbps", "gigabit", "gigabits"], Dimension::Data, 125_000_000.0),
+    // Temperature is affine; the factor is unused, see `to_kelvin`
+    (&["°C", "c", "celsius"], Dimension::Temperature, 0.0),
+    (&["°F", "f", "fahrenheit"], Dimension::Temperature, 0.0),
+    (&["K", "k", "kelvin"], Dimension::Temperature, 0.0),
+];
+
+const SEPARATORS: [&str; 4] = [" to ", " in ", " as ", " -> "];
+
+/// Try to read `query` as a unit or time zone conversion
+pub fn convert(query: &str) -> Option<Conversion> {
+    let query = query.trim();
+    // ASCII lowercasing keeps byte offsets valid for slicing `query`
+    let lower = query.to_ascii_lowercase();
+
+    let (from, to) = SEPARATORS.iter().find_map(|sep| {
+        let at = lower.rfind(sep)?;
+        Some((query[..at].trim(), query[at + sep.len()..].trim()))
+    })?;
+    if from.is_empty() || to.is_empty() {
+        return None;
+    }
+
+    convert_units(from, to).or_else(|| convert_time(from, to))
+}
+
+fn find_unit(name: &str) -> Option<(&'static str, Dimension, f64)> {
+    let name = name.trim();
+    UNITS
+        .iter()
+        .find(|(aliases, dimension, _)| aliases.iter().any(|alias| alias_matches(alias, *dimension, name)))
+        .map(|(aliases, dimension, factor)| (aliases[0], *dimension, *factor))
+}
+
+/// Case only matters for data size symbols, where `b`/`B` tell bits from bytes (`Mb`, `MB`);
+/// spelled-out names like `megabytes` or `Mbit` still match in any case
+fn alias_matches(alias: &str, dimension: Dimension, name: &str) -> bool {
+    if dimension == Dimension::Data && alias.len() <= 3 {
+        alias == name
+    } else {
+        alias.to_lowercase() == name.to_lowercase()
+    }
+}
+
+/// Split `5mi`, `5 mi` or `-3.5 °c` into the number and the unit
+fn split_quantity(input: &str) -> Option<(f64, &str)> {
+    let input = input.trim();
+    let end = input
+        .char_indices()
+        .find(|(i, c)| !(c.is_ascii_digit() || *c == '.' || *c == ',' || (*i == 0 && matches!(c, '-' | '+'))))
+        .map_or(input.len(), |(i, _)| i);
+
+    let number: String = input[..end].chars().filter(|c| *c != ',').collect();
+    let value = number.parse::<f64>().ok()?;
+    Some((value, input[end..].trim()))
+}
+
+fn convert_units(from: &str, to: &str) -> Option<Conversion> {
+    let (amount, from_name) = split_quantity(from)?;
+    let (from_label, from_dim, from_factor) = find_unit(from_name)?;
+    let (to_label, to_dim, to_factor) = find_unit(to)?;
+    if from_dim != to_dim {
+        return None;
+    }
+
+    let result = if from_dim == Dimension::Temperature {
+        from_kelvin(to_kelvin(amount, from_label), to_label)
+    } else {
+        amount * from_factor / to_factor
+    };
+
+    let value = format_amount(result);
+    Some(Conversion {
+        display: format!("{} {} = {} {}", format_amount(amount), from_label, value, to_label),
+        value,
+    })
+}
+
+fn to_kelvin(value: f64, unit: &str) -> f64 {
+    match unit {
+        "°C" => value + 273.15,
+        "°F" => (value - 32.0) * 5.0 / 9.0 + 273.15,
+        _ => value,
+    }
+}
+
+fn from_kelvin(kelvin: f64, unit: &str) -> f64 {
+    match unit {
+        "°C" => kelvin - 273.15,
+        "°F" => (kelvin - 273.15) * 9.0 / 5.0 + 32.0,
+        _ => kelvin,
+    }
+}
+
+/// Up to 6 significant decimals, without trailing zeros
+fn format_amount(value: f64) -> String {
+    if value != 0.0 && (value.abs() >= 1e15 || value.abs() < 1e-6) {
+        return format!("{:e}", value);
+    }
+    let formatted = format!("{:.6}", value);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    if trimmed == "-0" { "0".to_string() } else { trimmed.to_string() }
+}
+
+/// Source or target of a time conversion
+enum Zone {
+    Local,
+    Named(Tz),
+}
+
+impl Zone {
+    fn parse(name: &str) -> Option<Self> {
+        let name = name.trim();
+        match name.to_lowercase().as_str() {
+            "local" | "here" => return Some(Zone::Local),
+            "utc" | "gmt" | "z" => return Some(Zone::Named(Tz::UTC)),
+            _ => {}
+        }
+        // Allow spaces for underscores, e.g. "america/new york"
+        Tz::from_str_insensitive(&name.replace(' ', "_")).ok().map(Zone::Named)
+    }
+
+    fn label(&self) -> String {
+        match self {
+            Zone::Local => "local".to_string(),
+            Zone::Named(tz) => tz.name().to_string(),
+        }
+    }
+
+    /// Today's `time` in this zone, as an instant
+    fn resolve(&self, time: NaiveTime) -> Option<DateTime<Utc>> {
+        match self {
+            Zone::Local => {
+                let date = Local::now().date_naive();
+                Local
+                    .from_local_datetime(&NaiveDateTime::new(date, time))
+                    .earliest()
+                    .map(|t| t.with_timezone(&Utc))
+            }
+            Zone::Named(tz) => {
+                let date = Utc::now().with_timezone(tz).date_naive();
+                tz.from_local_datetime(&NaiveDateTime::new(date, time))
+                    .earliest()
+                    .map(|t| t.with_timezone(&Utc))
+            }
+        }
+    }
+
+    fn localize(&self, instant: DateTime<Utc>) -> NaiveDateTime {
+        match self {
+            Zone::Local => instant.with_timezone(&Local).naive_local(),
+            Zone::Named(tz) => instant.with_timezone(tz).naive_local(),
+        }
+    }
+}
+
+/// `15:00`, `3pm` or `3:30 pm`
+fn parse_time(input: &str) -> Option<NaiveTime> {
+    let input = input.trim().to_lowercase();
+    let (clock, offset) = if let Some(clock) = input.strip_suffix("pm") {
+        (clock.trim(), 12)
+    } else if let Some(clock) = input.strip_suffix("am") {
+        (clock.trim(), 0)
+    } else {
+        (input.as_str(), -1)
+    };
+
+    let (hour, minute) = match clock.split_once(':') {
+        Some((h, m)) => (h.parse::<u32>().ok()?, m.parse::<u32>().ok()?),
+        // A bare hour needs am/pm, otherwise "5 in ..." would look like a time
+        None if offset >= 0 => (clock.parse::<u32>().ok()?, 0),
+        None => return None,
+    };
+
+    let hour = match offset {
+        -1 => hour,
+        _ if hour == 0 || hour > 12 => return None,
+        _ => hour % 12 + offset as u32,
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+fn convert_time(from: &str, to: &str) -> Option<Conversion> {
+    // "15:00 UTC", "3pm Europe/Berlin", "now", or just "15:00" for local time
+    let (time, source) = match from.rsplit_once(char::is_whitespace) {
+        Some((time, zone)) => match (time.trim(), Zone::parse(zone)) {
+            (time, Some(zone)) if time.eq_ignore_ascii_case("now") => (None, zone),
+            (time, Some(zone)) => (Some(parse_time(time)?), zone),
+            (_, None) => (Some(parse_time(from)?), Zone::Local),
+        },
+        None if from.eq_ignore_ascii_case("now") => (None, Zone::Local),
+        None => (Some(parse_time(from)?), Zone::Local),
+    };
+    let target = Zone::parse(to)?;
+
+    let instant = match time {
+        Some(time) => source.resolve(time)?,
+        None => Utc::now(),
+    };
+    let source_time = source.localize(instant);
+    let target_time = target.localize(instant);
+
+    let value = target_time.format("%H:%M").to_string();
+    let day_shift = match (target_time.date() - source_time.date()).num_days() {
+        0 => String::new(),
+        1 => " (next day)".to_string(),
+        -1 => " (previous day)".to_string(),
+        n => format!(" ({:+} days)", n),
+    };
+
+    Some(Conversion {
+        display: format!(
+            "{} {} = {} {}{}",
+            source_time.format("%H:%M"),
+            source.label(),
+            value,
+            target.label(),
+            day_shift
+        ),
+        value,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value(query: &str) -> String {
+        convert(query).unwrap_or_else(|| panic!("{:?} doesn't convert", query)).value
+    }
+
+    #[test]
+    fn temperatures() {
+        assert_eq!(value("100 c to f"), "212");
+        assert_eq!(value("-40 °F in °C"), "-40");
+        assert_eq!(value("0 K to C"), "-273.15");
+        assert_eq!(value("32 fahrenheit to kelvin"), "273.15");
+        assert_eq!(convert("72 f in c").unwrap().display, "72 °F = 22.222222 °C");
+    }
+
+    #[test]
+    fn temperature_round_trips() {
+        for (from, to) in [("°C", "°F"), ("°F", "K"), ("K", "°C")] {
+            for amount in [-40.0, 0.0, 36.6, 451.0] {
+                let there: f64 = value(&format!("{} {} to {}", amount, from, to)).parse().unwrap();
+                let back: f64 = value(&format!("{} {} to {}", there, to, from)).parse().unwrap();
+                assert!((back - amount).abs() < 1e-4, "{} {} came back as {}", amount, from, back);
+            }
+        }
+    }
+
+    #[test]
+    fn bits_and_bytes_are_told_apart() {
+        assert_eq!(value("8 Mb to MB"), "1");
+        assert_eq!(value("1 MB to Mb"), "8");
+        assert_eq!(value("1 B in bits"), "8");
+        assert_eq!(value("16 b to B"), "2");
+        assert_eq!(value("100 mbps to MB"), "12.5");
+        assert_eq!(value("1 KiB to B"), "1024");
+        assert_eq!(value("1 GB to MiB"), "953.674316");
+        assert_eq!(value("2 Megabytes to kB"), "2000");
+        assert_eq!(convert("1 GB to Gbit").unwrap().display, "1 GB = 8 Gbit");
+        // Ambiguous casing isn't guessed at
+        assert_eq!(convert("5 mb to kb"), None);
+        assert_eq!(convert("5 Mib to B"), None);
+    }
+
+    #[test]
+    fn other_units_ignore_case() {
+        assert_eq!(value("5 MI to KM"), "8.04672");
+        assert_eq!(value("1,000 G in Kg"), "1");
+        assert_eq!(value("90 Min as H"), "1.5");
+        assert_eq!(convert("5 mi to kg"), None);
+    }
+}
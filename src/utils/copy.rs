@@ -9,5 +9,6 @@ pub fn copy_to_clipboard(text: &str) -> Result<(), Box<dyn std::error::Error>> {
         use std::io::Write;
         stdin.write_all(text.as_bytes())?;
     }
+    crate::utils::terminal::reap_in_background(child);
     Ok(())
 }
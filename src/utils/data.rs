@@ -153,3 +153,12 @@ pub fn delete_item(index: usize) -> bool {
         false
     }
 }
+
+/// Remove every item from the history.
+pub fn clear() {
+    let mut history = CLIPBOARD_HISTORY.write().unwrap();
+    let history = history.as_mut().expect("Clipboard history not initialized");
+
+    history.clear();
+    save_to_cache(history);
+}
@@ -0,0 +1,50 @@
+//! `--dmenu`: pick one of the lines read from stdin and print it, for use from scripts.
+
+use std::io::{self, BufRead, Write};
+use std::sync::OnceLock;
+
+/// Exit status when the picker is closed without choosing anything (dmenu uses 1 too)
+pub const EXIT_CANCELLED: i32 = 1;
+
+#[derive(Debug, Clone)]
+pub struct DmenuOptions {
+    /// Entries in input order, empty lines dropped
+    pub lines: Vec<String>,
+    /// Search bar placeholder from `-p` / `--prompt`
+    pub prompt: Option<String>,
+}
+
+static OPTIONS: OnceLock<DmenuOptions> = OnceLock::new();
+
+/// Read the entries from stdin; `prompt` comes from `-p` / `--prompt`
+pub fn init(prompt: Option<String>) {
+    let lines: Vec<String> = io::stdin()
+        .lock()
+        .lines()
+        .map_while(Result::ok)
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    eprintln!("[Dmenu] Read {} entries from stdin", lines.len());
+
+    let _ = OPTIONS.set(DmenuOptions { lines, prompt });
+}
+
+pub fn options() -> Option<&'static DmenuOptions> {
+    OPTIONS.get()
+}
+
+pub fn is_active() -> bool {
+    OPTIONS.get().is_some()
+}
+
+/// Print the choice for the calling script and exit successfully
+pub fn select(choice: &str) -> ! {
+    let mut stdout = io::stdout().lock();
+    let _ = writeln!(stdout, "{}", choice);
+    let _ = stdout.flush();
+    std::process::exit(0);
+}
+
+pub fn cancel() -> ! {
+    std::process::exit(EXIT_CANCELLED);
+}
@@ -0,0 +1,256 @@
+//! Index of files and folders under the home directory for the `/` file search mode.
+//! Served from the on-disk cache right away, then rebuilt on a rayon pool, and again on `refresh`.
+
+use arc_swap::ArcSwapOption;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::utils::normalize;
+
+const CACHE_FILE: &str = ".cache/sierra/files.cache";
+/// Directories deeper than this below home are not descended into
+const MAX_DEPTH: usize = 16;
+/// Hard cap so a huge home directory can't balloon memory or the cache file
+const MAX_ENTRIES: usize = 300_000;
+/// `refresh` skips the walk if the last one finished this recently
+const RESCAN_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Excluded when the config doesn't list its own, matched like .gitignore lines
+pub const DEFAULT_EXCLUDES: [&str; 6] = ["node_modules", "target", "__pycache__", ".git", ".cache", ".local/share/Trash"];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileIndexOptions {
+    pub include_hidden: bool,
+    pub excludes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileEntry {
+    pub path: PathBuf,
+    /// Folded file name (see `normalize::fold`), what the fuzzy matcher runs on
+    pub name_lower: String,
+    pub is_dir: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct FileIndexCache {
+    options: FileIndexOptions,
+    entries: Vec<FileEntry>,
+}
+
+static FILE_INDEX: ArcSwapOption<Vec<FileEntry>> = ArcSwapOption::const_empty();
+/// Bumped whenever FILE_INDEX is replaced
+static INDEX_GENERATION: AtomicU64 = AtomicU64::new(0);
+static STARTED: AtomicBool = AtomicBool::new(false);
+static SCANNING: AtomicBool = AtomicBool::new(false);
+static LAST_SCAN: Mutex<Option<Instant>> = Mutex::new(None);
+
+fn get_cache_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(CACHE_FILE)
+}
+
+fn load_from_cache(options: &FileIndexOptions) -> Option<Vec<FileEntry>> {
+    let content = fs::read(get_cache_path()).ok()?;
+    let cache = bincode::deserialize::<FileIndexCache>(&content).ok()?;
+    // Different hidden/exclude settings would show stale results
+    (cache.options == *options).then_some(cache.entries)
+}
+
+fn save_to_cache(options: &FileIndexOptions, entries: &[FileEntry]) {
+    let path = get_cache_path();
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let cache = FileIndexCache {
+        options: options.clone(),
+        entries: entries.to_vec(),
+    };
+    if let Ok(encoded) = bincode::serialize(&cache) {
+        if let Err(e) = fs::write(&path, encoded) {
+            eprintln!("[FileIndex] Failed to save cache: {}", e);
+        }
+    }
+}
+
+fn store(entries: Vec<FileEntry>) {
+    FILE_INDEX.store(Some(Arc::new(entries)));
+    INDEX_GENERATION.fetch_add(1, Ordering::Release);
+}
+
+/// Load the cached index and start a fresh scan of home (only the first call does anything)
+pub fn start(options: FileIndexOptions) {
+    if STARTED.swap(true, Ordering::AcqRel) {
+        return;
+    }
+    let Some(home) = dirs::home_dir() else {
+        return;
+    };
+
+    thread::spawn(move || {
+        if let Some(entries) = load_from_cache(&options) {
+            eprintln!("[FileIndex] Loaded {} entries from cache", entries.len());
+            store(entries);
+        }
+        scan_home(&home, &options);
+    });
+}
+
+/// Rescan home once started, e.g. when the daemon shows the launcher; the old index stays until then
+pub fn refresh(options: FileIndexOptions) {
+    if !STARTED.load(Ordering::Acquire) {
+        return;
+    }
+    if LAST_SCAN.lock().unwrap().is_some_and(|last| last.elapsed() < RESCAN_INTERVAL) {
+        return;
+    }
+    let Some(home) = dirs::home_dir() else {
+        return;
+    };
+    thread::spawn(move || scan_home(&home, &options));
+}
+
+fn scan_home(home: &Path, options: &FileIndexOptions) {
+    if SCANNING.swap(true, Ordering::AcqRel) {
+        return;
+    }
+
+    let start = Instant::now();
+    let walk = Walk {
+        excludes: build_excludes(home, &options.excludes),
+        include_hidden: options.include_hidden,
+        found: AtomicUsize::new(0),
+    };
+    let mut entries = walk.scan_dir(home, 0, &[]);
+    // Directories scanned in parallel can overshoot the cap a little
+    entries.truncate(MAX_ENTRIES);
+    eprintln!("[FileIndex] ✓ Indexed {} entries in {:?}", entries.len(), start.elapsed());
+
+    save_to_cache(options, &entries);
+    store(entries);
+    *LAST_SCAN.lock().unwrap() = Some(Instant::now());
+    SCANNING.store(false, Ordering::Release);
+}
+
+/// Current index, or `None` before the cache or first scan is available
+pub fn entries() -> Option<Arc<Vec<FileEntry>>> {
+    FILE_INDEX.load_full()
+}
+
+pub fn generation() -> u64 {
+    INDEX_GENERATION.load(Ordering::Acquire)
+}
+
+fn build_excludes(home: &Path, patterns: &[String]) -> Gitignore {
+    let mut builder = GitignoreBuilder::new(home);
+    for pattern in patterns {
+        if let Err(e) = builder.add_line(None, pattern) {
+            eprintln!("[FileIndex] Invalid exclude {:?}: {}", pattern, e);
+        }
+    }
+    builder.build().unwrap_or_else(|_| Gitignore::empty())
+}
+
+/// `.gitignore` and `.ignore` of `dir`, if it has any
+fn dir_ignores(dir: &Path) -> Option<Arc<Gitignore>> {
+    let mut builder = GitignoreBuilder::new(dir);
+    let mut found = false;
+    for name in [".gitignore", ".ignore"] {
+        let file = dir.join(name);
+        if file.is_file() {
+            found |= builder.add(file).is_none();
+        }
+    }
+    if !found {
+        return None;
+    }
+    builder.build().ok().map(Arc::new)
+}
+
+/// Innermost ignore file decides, like git does for nested .gitignore files
+fn is_ignored(path: &Path, is_dir: bool, ignores: &[Arc<Gitignore>], excludes: &Gitignore) -> bool {
+    if excludes.matched(path, is_dir).is_ignore() {
+        return true;
+    }
+    for gitignore in ignores.iter().rev() {
+        let matched = gitignore.matched(path, is_dir);
+        if matched.is_ignore() {
+            return true;
+        }
+        if matched.is_whitelist() {
+            return false;
+        }
+    }
+    false
+}
+
+/// One scan of home, shared by the directories it walks in parallel
+struct Walk {
+    excludes: Gitignore,
+    include_hidden: bool,
+    /// Entries found so far; the walk winds down once it reaches MAX_ENTRIES
+    found: AtomicUsize,
+}
+
+impl Walk {
+    /// Entries below `dir`; subdirectories are scanned in parallel on the rayon pool
+    fn scan_dir(&self, dir: &Path, depth: usize, parent_ignores: &[Arc<Gitignore>]) -> Vec<FileEntry> {
+        if self.found.load(Ordering::Relaxed) >= MAX_ENTRIES {
+            return Vec::new();
+        }
+        let Ok(read_dir) = fs::read_dir(dir) else {
+            return Vec::new();
+        };
+
+        let mut ignores = parent_ignores.to_vec();
+        ignores.extend(dir_ignores(dir));
+
+        let mut entries = Vec::new();
+        let mut subdirs = Vec::new();
+
+        for entry in read_dir.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !self.include_hidden && name.starts_with('.') {
+                continue;
+            }
+            // Symlinks are listed but not followed, which keeps loops out of the walk
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let path = entry.path();
+            let is_dir = file_type.is_dir();
+            if is_ignored(&path, is_dir, &ignores, &self.excludes) {
+                continue;
+            }
+
+            if is_dir && depth < MAX_DEPTH {
+                subdirs.push(path.clone());
+            }
+            entries.push(FileEntry {
+                path,
+                name_lower: normalize::fold(&name),
+                is_dir,
+            });
+        }
+
+        self.found.fetch_add(entries.len(), Ordering::Relaxed);
+
+        let nested = subdirs
+            .par_iter()
+            .map(|sub| self.scan_dir(sub, depth + 1, &ignores))
+            .reduce(Vec::new, |mut a, b| {
+                a.extend(b);
+                a
+            });
+        entries.extend(nested);
+        entries
+    }
+}
@@ -0,0 +1,116 @@
+//! Launch history for ranking frequently and recently used entries.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::RwLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const CACHE_FILE: &str = ".cache/sierra/frecency.cache";
+/// Number of recent launch timestamps kept per entry
+const MAX_TIMESTAMPS: usize = 10;
+
+const DAY: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct LaunchStats {
+    count: u32,
+    /// Unix timestamps (seconds) of the most recent launches, newest first
+    recent: Vec<u64>,
+}
+
+/// Global launch history, keyed by entry id (e.g. desktop file id).
+static LAUNCH_HISTORY: RwLock<Option<HashMap<String, LaunchStats>>> = RwLock::new(None);
+
+fn get_cache_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(CACHE_FILE)
+}
+
+fn load_from_cache() -> HashMap<String, LaunchStats> {
+    let path = get_cache_path();
+    if let Ok(content) = fs::read(&path) {
+        if let Ok(stats) = bincode::deserialize::<HashMap<String, LaunchStats>>(&content) {
+            eprintln!("Loaded {} entries from frecency cache", stats.len());
+            return stats;
+        }
+    }
+    HashMap::new()
+}
+
+fn save_to_cache(history: &HashMap<String, LaunchStats>) {
+    let path = get_cache_path();
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Ok(encoded) = bincode::serialize(history) {
+        if let Err(e) = fs::write(&path, encoded) {
+            eprintln!("Failed to save frecency cache: {}", e);
+        }
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Initialize the launch history storage.
+pub fn init() {
+    let mut history = LAUNCH_HISTORY.write().unwrap();
+    if history.is_none() {
+        *history = Some(load_from_cache());
+    }
+}
+
+/// Record a launch of `id` and persist the history immediately,
+/// since the launcher usually exits right after launching.
+pub fn record_launch(id: &str) {
+    let mut history = LAUNCH_HISTORY.write().unwrap();
+    let history = history.get_or_insert_with(load_from_cache);
+
+    let stats = history.entry(id.to_string()).or_default();
+    stats.count = stats.count.saturating_add(1);
+    stats.recent.insert(0, now_secs());
+    stats.recent.truncate(MAX_TIMESTAMPS);
+
+    save_to_cache(history);
+}
+
+/// Frecency score for `id`: launch count weighted by how recent the last launches were.
+/// Returns 0.0 for entries that were never launched.
+pub fn score(id: &str) -> f64 {
+    let history = LAUNCH_HISTORY.read().unwrap();
+    let Some(stats) = history.as_ref().and_then(|h| h.get(id)) else {
+        return 0.0;
+    };
+
+    if stats.recent.is_empty() {
+        return 0.0;
+    }
+
+    let now = now_secs();
+    let weight_sum: f64 = stats
+        .recent
+        .iter()
+        .map(|&ts| match now.saturating_sub(ts) {
+            age if age < 4 * DAY => 100.0,
+            age if age < 14 * DAY => 70.0,
+            age if age < 31 * DAY => 50.0,
+            age if age < 90 * DAY => 30.0,
+            _ => 10.0,
+        })
+        .sum();
+
+    stats.count as f64 * weight_sum / stats.recent.len() as f64
+}
+
+/// Frecency mapped onto the fuzzy matcher's score range, so it can be
+/// added to match scores without drowning out match quality.
+pub fn bonus(id: &str) -> i64 {
+    (score(id).ln_1p() * 15.0) as i64
+}
@@ -0,0 +1,290 @@
+//! Freedesktop icon theme lookup with an on-disk cache of decoded, resized icons.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Edge length (pixels) icons are rasterized and cached at
+pub const ICON_SIZE: u32 = 32;
+
+/// Scalable first: an SVG rendered at ICON_SIZE stays sharp, a resized PNG doesn't
+const EXTENSIONS: [&str; 2] = ["svg", "png"];
+
+/// Bumped when lookup picks different files, so stale cached icons aren't reused
+const CACHE_VERSION: u32 = 2;
+
+/// Resolves icons on a worker thread so the UI never blocks on disk or decoding.
+pub struct IconLoader {
+    /// icon name -> cached PNG, `None` once lookup failed
+    resolved: Arc<Mutex<HashMap<String, Option<PathBuf>>>>,
+    requested: Mutex<HashSet<String>>,
+    sender: Sender<String>,
+}
+
+impl IconLoader {
+    pub fn new() -> Self {
+        let resolved: Arc<Mutex<HashMap<String, Option<PathBuf>>>> =
+            Arc::new(Mutex::new(HashMap::new()));
+        let (sender, receiver) = channel::<String>();
+
+        let resolved_clone = Arc::clone(&resolved);
+        thread::spawn(move || {
+            let theme = IconTheme::detect();
+            let cache_dir = Self::cache_dir(&theme.name);
+            let _ = fs::create_dir_all(&cache_dir);
+
+            while let Ok(icon) = receiver.recv() {
+                let cached = Self::load_cached(&theme, &cache_dir, &icon);
+                if let Ok(mut resolved) = resolved_clone.lock() {
+                    resolved.insert(icon, cached);
+                }
+            }
+        });
+
+        Self {
+            resolved,
+            requested: Mutex::new(HashSet::new()),
+            sender,
+        }
+    }
+
+    /// Cached PNG for `icon`, queueing a lookup the first time it is asked for
+    pub fn get(&self, icon: &str) -> Option<PathBuf> {
+        if let Some(result) = self.resolved.lock().ok()?.get(icon) {
+            return result.clone();
+        }
+
+        if let Ok(mut requested) = self.requested.lock() {
+            if requested.insert(icon.to_string()) {
+                let _ = self.sender.send(icon.to_string());
+            }
+        }
+        None
+    }
+
+    /// ~/.cache/sierra/icons/v<version>/<theme>/<size>
+    fn cache_dir(theme: &str) -> PathBuf {
+        dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(".cache/sierra/icons")
+            .join(format!("v{}", CACHE_VERSION))
+            .join(theme)
+            .join(ICON_SIZE.to_string())
+    }
+
+    fn load_cached(theme: &IconTheme, cache_dir: &Path, icon: &str) -> Option<PathBuf> {
+        let cached = cache_dir.join(format!("{}.png", icon.trim_start_matches('/').replace('/', "_")));
+        if cached.exists() {
+            return Some(cached);
+        }
+
+        let source = theme.lookup(icon)?;
+        let image = Self::decode(&source)?;
+        let image = if image.width() != ICON_SIZE || image.height() != ICON_SIZE {
+            image.resize(ICON_SIZE, ICON_SIZE, image::imageops::FilterType::Lanczos3)
+        } else {
+            image
+        };
+
+        if let Err(e) = image.save_with_format(&cached, image::ImageFormat::Png) {
+            eprintln!("[Icons] Failed to cache {:?}: {}", cached, e);
+            return None;
+        }
+        Some(cached)
+    }
+
+    fn decode(path: &Path) -> Option<image::DynamicImage> {
+        if path.extension().and_then(|e| e.to_str()) == Some("svg") {
+            return Self::render_svg(path);
+        }
+
+        image::ImageReader::open(path).ok()?.decode().ok()
+    }
+
+    fn render_svg(path: &Path) -> Option<image::DynamicImage> {
+        let data = fs::read(path).ok()?;
+        let tree = resvg::usvg::Tree::from_data(&data, &resvg::usvg::Options::default()).ok()?;
+
+        let size = tree.size();
+        let scale = ICON_SIZE as f32 / size.width().max(size.height());
+        let mut pixmap = resvg::tiny_skia::Pixmap::new(ICON_SIZE, ICON_SIZE)?;
+        resvg::render(
+            &tree,
+            resvg::tiny_skia::Transform::from_scale(scale, scale),
+            &mut pixmap.as_mut(),
+        );
+
+        // tiny-skia stores premultiplied alpha
+        let pixels: Vec<u8> = pixmap
+            .pixels()
+            .iter()
+            .flat_map(|p| {
+                let c = p.demultiply();
+                [c.red(), c.green(), c.blue(), c.alpha()]
+            })
+            .collect();
+
+        image::RgbaImage::from_raw(ICON_SIZE, ICON_SIZE, pixels).map(image::DynamicImage::ImageRgba8)
+    }
+}
+
+/// The user's icon theme plus everything it inherits from, ending in hicolor.
+struct IconTheme {
+    name: String,
+    /// Theme directories in lookup order, each with its (subdir, nominal size) list
+    chain: Vec<(PathBuf, Vec<(String, u32)>)>,
+}
+
+impl IconTheme {
+    fn detect() -> Self {
+        let name = Self::configured_theme().unwrap_or_else(|| "hicolor".to_string());
+        let mut chain = Vec::new();
+        let mut seen = HashSet::new();
+        Self::collect(&name, &mut chain, &mut seen);
+        if !seen.contains("hicolor") {
+            Self::collect("hicolor", &mut chain, &mut seen);
+        }
+
+        eprintln!("[Icons] Using icon theme {:?}", name);
+        Self { name, chain }
+    }
+
+    /// `gtk-icon-theme-name` from the GTK settings, which is what most Wayland setups configure
+    fn configured_theme() -> Option<String> {
+        let config = dirs::config_dir()?;
+        ["gtk-4.0/settings.ini", "gtk-3.0/settings.ini"]
+            .iter()
+            .filter_map(|f| fs::read_to_string(config.join(f)).ok())
+            .find_map(|content| {
+                content.lines().find_map(|line| {
+                    let (key, value) = line.split_once('=')?;
+                    (key.trim() == "gtk-icon-theme-name")
+                        .then(|| value.trim().trim_matches('"').to_string())
+                })
+            })
+    }
+
+    /// ~/.icons, then `icons` in $XDG_DATA_HOME and each of $XDG_DATA_DIRS
+    fn base_dirs() -> Vec<PathBuf> {
+        let mut dirs_list = Vec::new();
+        if let Some(home) = dirs::home_dir() {
+            dirs_list.push(home.join(".icons"));
+        }
+        if let Some(data) = dirs::data_dir() {
+            dirs_list.push(data.join("icons"));
+        }
+        let data_dirs = std::env::var("XDG_DATA_DIRS")
+            .ok()
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+        dirs_list.extend(
+            data_dirs
+                .split(':')
+                .filter(|d| !d.is_empty())
+                .map(|d| PathBuf::from(d).join("icons")),
+        );
+        dirs_list
+    }
+
+    fn collect(name: &str, chain: &mut Vec<(PathBuf, Vec<(String, u32)>)>, seen: &mut HashSet<String>) {
+        if !seen.insert(name.to_string()) {
+            return;
+        }
+
+        let mut inherits = Vec::new();
+        for base in Self::base_dirs() {
+            let theme_dir = base.join(name);
+            let Ok(index) = fs::read_to_string(theme_dir.join("index.theme")) else {
+                continue;
+            };
+            let (subdirs, parents) = Self::parse_index(&index);
+            if inherits.is_empty() {
+                inherits = parents;
+            }
+            chain.push((theme_dir, subdirs));
+        }
+
+        for parent in inherits {
+            Self::collect(&parent, chain, seen);
+        }
+    }
+
+    /// Returns the `Directories` with their nominal size, and the `Inherits` list
+    fn parse_index(index: &str) -> (Vec<(String, u32)>, Vec<String>) {
+        let mut directories: Vec<String> = Vec::new();
+        let mut inherits = Vec::new();
+        let mut sizes: HashMap<String, u32> = HashMap::new();
+        let mut section = String::new();
+
+        for line in index.lines().map(str::trim) {
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = name.to_string();
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let (key, value) = (key.trim(), value.trim());
+
+            if section == "Icon Theme" {
+                match key {
+                    "Directories" | "ScaledDirectories" => directories
+                        .extend(value.split(',').map(|d| d.trim().to_string()).filter(|d| !d.is_empty())),
+                    "Inherits" => inherits = value.split(',').map(|t| t.trim().to_string()).collect(),
+                    _ => {}
+                }
+            } else if key == "Size" {
+                if let Ok(size) = value.parse() {
+                    sizes.insert(section.clone(), size);
+                }
+            }
+        }
+
+        let subdirs = directories
+            .into_iter()
+            .map(|d| {
+                let size = sizes.get(&d).copied().unwrap_or(0);
+                (d, size)
+            })
+            .collect();
+        (subdirs, inherits)
+    }
+
+    /// Find the file for an icon name or absolute path, preferring scalable icons,
+    /// then sizes closest to ICON_SIZE
+    fn lookup(&self, icon: &str) -> Option<PathBuf> {
+        let path = Path::new(icon);
+        if path.is_absolute() {
+            return path.is_file().then(|| path.to_path_buf());
+        }
+
+        for (theme_dir, subdirs) in &self.chain {
+            let best = subdirs
+                .iter()
+                .filter_map(|(subdir, size)| {
+                    EXTENSIONS
+                        .iter()
+                        .map(|ext| theme_dir.join(subdir).join(format!("{}.{}", icon, ext)))
+                        .find(|p| p.is_file())
+                        .map(|p| {
+                            let bitmap = p.extension().and_then(|e| e.to_str()) != Some("svg");
+                            (p, (bitmap, size.abs_diff(ICON_SIZE)))
+                        })
+                })
+                .min_by_key(|(_, rank)| *rank);
+
+            if let Some((path, _)) = best {
+                return Some(path);
+            }
+        }
+
+        // Legacy location for apps that don't ship themed icons
+        EXTENSIONS
+            .iter()
+            .map(|ext| PathBuf::from("/usr/share/pixmaps").join(format!("{}.{}", icon, ext)))
+            .find(|p| p.is_file())
+    }
+}
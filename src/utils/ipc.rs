@@ -0,0 +1,112 @@
+//! Control socket of a `--daemon` launcher, and the client side used by `sierra-launcher toggle`.
+//!
+//! Requests and replies are single lines; what they mean is up to the launcher (see `app::daemon`).
+
+use std::fs::{self, DirBuilder, Permissions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::os::unix::fs::{DirBuilderExt, MetadataExt, PermissionsExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+/// Clients give up on a daemon that doesn't answer within this
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Listener bound by `bind`, handed to the subscription that serves it
+static LISTENER: Mutex<Option<UnixListener>> = Mutex::new(None);
+
+/// `$XDG_RUNTIME_DIR`, or a directory of our own in /tmp without one
+fn socket_dir() -> PathBuf {
+    match std::env::var_os("XDG_RUNTIME_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(format!("/tmp/sierra-{}", unsafe { libc::getuid() })),
+    }
+}
+
+/// `sierra.sock` in `socket_dir`
+pub fn socket_path() -> PathBuf {
+    socket_dir().join("sierra.sock")
+}
+
+/// Create the /tmp fallback directory accessible only to us, refusing one anybody else could use
+fn ensure_private_dir(dir: &Path) -> io::Result<()> {
+    match DirBuilder::new().mode(0o700).create(dir) {
+        Ok(()) => return Ok(()),
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+        Err(e) => return Err(e),
+    }
+    let metadata = fs::symlink_metadata(dir)?;
+    if !metadata.is_dir() || metadata.uid() != unsafe { libc::getuid() } || metadata.mode() & 0o077 != 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("{} is not a private directory of this user", dir.display()),
+        ));
+    }
+    Ok(())
+}
+
+/// Claim the control socket for this process. Fails if another daemon is answering on it;
+/// a socket file left behind by a crashed daemon is replaced.
+pub fn bind() -> io::Result<()> {
+    if std::env::var_os("XDG_RUNTIME_DIR").is_none() {
+        ensure_private_dir(&socket_dir())?;
+    }
+    let path = socket_path();
+    if path.exists() {
+        if UnixStream::connect(&path).is_ok() {
+            return Err(io::Error::new(
+                io::ErrorKind::AddrInUse,
+                format!("a Sierra daemon is already listening on {}", path.display()),
+            ));
+        }
+        fs::remove_file(&path)?;
+    }
+
+    let listener = UnixListener::bind(&path)?;
+    // The socket hands out clipboard history, so only our own user may connect
+    fs::set_permissions(&path, Permissions::from_mode(0o600))?;
+    eprintln!("[Ipc] Listening on {}", path.display());
+    *LISTENER.lock().unwrap() = Some(listener);
+    Ok(())
+}
+
+/// Take the bound listener; only the first caller gets it
+pub fn take_listener() -> Option<UnixListener> {
+    LISTENER.lock().unwrap().take()
+}
+
+/// Accept connections forever, answering each request line with what `handle` returns.
+/// Every client gets its own thread, so a slow one doesn't hold up the others.
+pub fn serve(listener: UnixListener, handle: impl Fn(&str) -> String + Clone + Send + 'static) {
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let handle = handle.clone();
+        thread::spawn(move || {
+            let _ = stream.set_read_timeout(Some(CLIENT_TIMEOUT));
+
+            let mut request = String::new();
+            if BufReader::new(&stream).read_line(&mut request).is_err() {
+                return;
+            }
+
+            let reply = handle(request.trim());
+            let _ = writeln!(&stream, "{}", reply);
+        });
+    }
+}
+
+/// Send one request to the running daemon and return its answer
+pub fn send(request: &str) -> io::Result<String> {
+    let stream = UnixStream::connect(socket_path())?;
+    stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+    stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+
+    writeln!(&stream, "{}", request)?;
+    let mut reply = String::new();
+    BufReader::new(&stream).read_line(&mut reply)?;
+    Ok(reply.trim().to_string())
+}
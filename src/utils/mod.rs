@@ -4,4 +4,20 @@ pub mod monitor;
 pub mod copy;
 pub mod theme;
 pub mod watcher;
-pub mod wallpaper_manager;
\ No newline at end of file
+pub mod wallpaper_manager;
+pub mod frecency;
+pub mod icons;
+pub mod normalize;
+pub mod terminal;
+pub mod path_index;
+pub mod calculator;
+pub mod convert;
+pub mod file_index;
+pub mod recent;
+pub mod quicklinks;
+pub mod symbols;
+pub mod compositor;
+pub mod ssh;
+pub mod plugins;
+pub mod dmenu;
+pub mod ipc;
@@ -0,0 +1,76 @@
+//! Text folding for locale-independent matching.
+
+use unicode_normalization::char::{decompose_canonical, is_combining_mark};
+
+/// Lowercase and strip diacritics ("Éditeur" -> "editeur"); diacritics are only stripped
+/// from Latin, Greek and Cyrillic letters (see `has_strippable_marks`).
+///
+/// Folding is done per character and always yields exactly one output char
+/// per input char, so match positions found in the folded text can be used
+/// to highlight the original text.
+pub fn fold(text: &str) -> String {
+    text.chars().map(fold_char).collect()
+}
+
+fn fold_char(c: char) -> char {
+    let mut base = None;
+    if has_strippable_marks(c) {
+        decompose_canonical(c, |d| {
+            if base.is_none() && !is_combining_mark(d) {
+                base = Some(d);
+            }
+        });
+    }
+
+    let base = base.unwrap_or(c);
+    let mut lower = base.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(l), None) => l,
+        // Multi-char lowercase forms (e.g. 'İ') would break position mapping
+        _ => base,
+    }
+}
+
+/// Latin, Greek and Cyrillic, where the decomposed base is the letter people type.
+/// Elsewhere it is a different letter: Hangul syllables decompose into their leading jamo,
+/// and kana would lose the voicing marks that tell が from か.
+fn has_strippable_marks(c: char) -> bool {
+    matches!(c, '\u{00C0}'..='\u{052F}' | '\u{1E00}'..='\u{1FFF}')
+}
+
+/// Primary LC_MESSAGES locale as resolved by glib from LANGUAGE, LC_ALL, LC_MESSAGES and LANG,
+/// which is also what picks `Name[xx]` from desktop files
+pub fn messages_locale() -> String {
+    gio::glib::language_names_with_category("LC_MESSAGES")
+        .first()
+        .map(|l| l.to_string())
+        .unwrap_or_else(|| "C".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_latin_greek_and_cyrillic_marks() {
+        assert_eq!(fold("Éditeur de Texte"), "editeur de texte");
+        assert_eq!(fold("Ångström Ṩ"), "angstrom s");
+        assert_eq!(fold("Άλφα"), "αλφα");
+        assert_eq!(fold("Йод Ёлка"), "иод елка");
+    }
+
+    #[test]
+    fn keeps_hangul_syllables_and_kana_whole() {
+        // 한 and 하 share their leading jamo
+        assert_eq!(fold("한글"), "한글");
+        assert_ne!(fold("한"), fold("하"));
+        assert_eq!(fold("ガイド"), "ガイド");
+    }
+
+    #[test]
+    fn keeps_one_char_per_char() {
+        for text in ["İstanbul", "ﬁle", "한국어 사전", "Ça va"] {
+            assert_eq!(fold(text).chars().count(), text.chars().count(), "{}", text);
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! Index of executables found in `$PATH`, built in the background and rebuilt on `refresh`.
+
+use arc_swap::ArcSwapOption;
+use std::collections::HashSet;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+#[derive(Debug, Clone)]
+pub struct Executable {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+static PATH_INDEX: ArcSwapOption<Vec<Executable>> = ArcSwapOption::const_empty();
+static STARTED: AtomicBool = AtomicBool::new(false);
+static SCANNING: AtomicBool = AtomicBool::new(false);
+
+/// Start indexing `$PATH` on a background thread (only the first call does anything)
+pub fn start() {
+    if !STARTED.swap(true, Ordering::AcqRel) {
+        scan();
+    }
+}
+
+/// Rescan once started, e.g. when the daemon shows the launcher; the old index stays until then
+pub fn refresh() {
+    if STARTED.load(Ordering::Acquire) {
+        scan();
+    }
+}
+
+fn scan() {
+    if SCANNING.swap(true, Ordering::AcqRel) {
+        return;
+    }
+
+    thread::spawn(|| {
+        let start = std::time::Instant::now();
+        let executables = scan_path();
+        eprintln!("[PathIndex] ✓ Indexed {} executables in {:?}", executables.len(), start.elapsed());
+        PATH_INDEX.store(Some(Arc::new(executables)));
+        SCANNING.store(false, Ordering::Release);
+    });
+}
+
+/// Indexed executables, or `None` while the scan is still running
+pub fn executables() -> Option<Arc<Vec<Executable>>> {
+    PATH_INDEX.load_full()
+}
+
+/// Executables in PATH order; earlier directories shadow later ones like the shell does
+fn scan_path() -> Vec<Executable> {
+    let Some(paths) = std::env::var_os("PATH") else {
+        return Vec::new();
+    };
+
+    let mut seen = HashSet::new();
+    let mut executables = Vec::new();
+
+    for dir in std::env::split_paths(&paths) {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if seen.contains(&name) {
+                continue;
+            }
+
+            // Follows symlinks, which is how most of /usr/bin and ~/.local/bin is populated
+            let Ok(metadata) = fs::metadata(entry.path()) else {
+                continue;
+            };
+            if metadata.is_file() && metadata.permissions().mode() & 0o111 != 0 {
+                seen.insert(name.clone());
+                executables.push(Executable {
+                    name,
+                    path: entry.path(),
+                });
+            }
+        }
+    }
+
+    executables.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+    executables
+}
@@ -0,0 +1,455 @@
+//! Script plugins: external executables that provide results for a trigger prefix.
+//!
+//! A plugin is started on first use and kept running. Sierra writes one JSON object per line
+//! to its stdin and reads one JSON object per line from its stdout:
+//!
+//! - `{"type":"query","id":3,"query":"text"}` asks for results; answer with
+//!   `{"type":"results","id":3,"items":[{"title":"...","subtitle":"...","icon":"...","action":...}]}`
+//!   or `{"type":"error","message":"..."}`. Answers to older ids than the newest one shown are dropped.
+//! - `{"type":"activate","item":{...}}` hands back the chosen item; stdin is closed afterwards
+//!   and the plugin should carry out the action and exit. The next query starts it again.
+//!
+//! Each plugin runs on its own worker thread, so a slow, hung or crashed plugin only ever
+//! costs its own results and never blocks the UI.
+
+use iced::futures::channel::oneshot;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, SendError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::utils::terminal;
+
+/// Keystrokes closer together than this only send the last query
+const DEBOUNCE: Duration = Duration::from_millis(120);
+const DEFAULT_TIMEOUT_MS: u64 = 2000;
+/// Crashes tolerated before a plugin stays disabled for the session
+const MAX_RESTARTS: u32 = 3;
+
+fn default_timeout_ms() -> u64 {
+    DEFAULT_TIMEOUT_MS
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct PluginConfig {
+    pub name: String,
+    /// First word of the query that routes it to the plugin, e.g. `pw` for `pw github`
+    pub prefix: String,
+    /// Command line of the executable; `~/` is expanded
+    pub command: String,
+    /// How long a query may go unanswered before the plugin is restarted
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PluginItem {
+    pub title: String,
+    #[serde(default)]
+    pub subtitle: String,
+    /// Icon name from the icon theme
+    #[serde(default)]
+    pub icon: Option<String>,
+    /// Opaque value handed back to the plugin on activation
+    #[serde(default)]
+    pub action: serde_json::Value,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PluginStatus {
+    Idle,
+    Waiting,
+    Ready,
+    Failed(String),
+}
+
+/// What the plugin last answered, shared between its worker threads and the UI
+#[derive(Debug, Clone)]
+pub struct PluginState {
+    /// Bumped whenever `items` or `status` change
+    pub generation: u64,
+    /// Id of the query `items` answer
+    pub answered: u64,
+    pub items: Vec<PluginItem>,
+    pub status: PluginStatus,
+    /// Bumped for every process started; only its reader may update the state
+    process: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum Response {
+    Results { id: u64, items: Vec<PluginItem> },
+    Error { message: String },
+}
+
+enum Job {
+    Query(u64, String),
+    /// Chosen item, and where to report once the plugin is done with it
+    Activate(PluginItem, oneshot::Sender<bool>),
+}
+
+pub struct Plugin {
+    pub config: PluginConfig,
+    state: Arc<Mutex<PluginState>>,
+    /// Worker thread, spawned on the first query after startup or an activation
+    jobs: Option<Sender<Job>>,
+    next_id: u64,
+}
+
+impl Plugin {
+    pub fn new(config: PluginConfig) -> Self {
+        Self {
+            config,
+            state: Arc::new(Mutex::new(PluginState {
+                generation: 0,
+                answered: 0,
+                items: Vec::new(),
+                status: PluginStatus::Idle,
+                process: 0,
+            })),
+            jobs: None,
+            next_id: 0,
+        }
+    }
+
+    /// Ask for results; the worker debounces rapid calls
+    pub fn query(&mut self, query: &str) {
+        self.next_id += 1;
+        let job = Job::Query(self.next_id, query.to_string());
+        let job = match &self.jobs {
+            Some(jobs) => match jobs.send(job) {
+                Ok(()) => return,
+                // The worker is gone; start a new one
+                Err(SendError(job)) => job,
+            },
+            None => job,
+        };
+        let _ = self.spawn_worker().send(job);
+    }
+
+    fn spawn_worker(&mut self) -> &Sender<Job> {
+        let (sender, receiver) = mpsc::channel();
+        let worker = Worker::new(self.config.clone(), Arc::clone(&self.state));
+        thread::spawn(move || worker.run(receiver));
+        self.jobs.insert(sender)
+    }
+
+    pub fn state(&self) -> PluginState {
+        self.state.lock().unwrap().clone()
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.state.lock().unwrap().generation
+    }
+
+    /// Hand the item to the plugin. Resolves to whether it took the item, once it exited or
+    /// had the timeout to act on it.
+    pub fn activate(&mut self, item: &PluginItem) -> impl Future<Output = bool> {
+        let (done, result) = oneshot::channel();
+        // The worker stops with the plugin after an activation
+        if let Some(jobs) = self.jobs.take() {
+            let _ = jobs.send(Job::Activate(item.clone(), done));
+        }
+        // A dropped sender (no worker, or it went away) means the item wasn't handled
+        async move { result.await.unwrap_or(false) }
+    }
+}
+
+/// Words of `query` after the prefix of the plugin it triggers: `(plugin index, rest)`
+pub fn find(plugins: &[Plugin], query: &str) -> Option<(usize, String)> {
+    let (prefix, rest) = query.trim_start().split_once(char::is_whitespace)?;
+    plugins
+        .iter()
+        .position(|p| p.config.prefix == prefix)
+        .map(|idx| (idx, rest.trim().to_string()))
+}
+
+struct Running {
+    child: Child,
+    stdin: ChildStdin,
+}
+
+struct Worker {
+    config: PluginConfig,
+    state: Arc<Mutex<PluginState>>,
+    running: Option<Running>,
+    restarts: u32,
+    /// Sent queries not answered yet, oldest first
+    outstanding: VecDeque<(u64, Instant)>,
+}
+
+impl Worker {
+    fn new(config: PluginConfig, state: Arc<Mutex<PluginState>>) -> Self {
+        Self {
+            config,
+            state,
+            running: None,
+            restarts: 0,
+            outstanding: VecDeque::new(),
+        }
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_millis(self.config.timeout_ms)
+    }
+
+    fn run(mut self, jobs: Receiver<Job>) {
+        loop {
+            let job = match self.deadline() {
+                Some(deadline) => match jobs.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                    Ok(job) => Some(job),
+                    Err(RecvTimeoutError::Timeout) => None,
+                    Err(RecvTimeoutError::Disconnected) => break,
+                },
+                None => match jobs.recv() {
+                    Ok(job) => Some(job),
+                    Err(_) => break,
+                },
+            };
+
+            match job {
+                None => self.check_timeout(),
+                Some(Job::Query(id, query)) => {
+                    let Some(job) = self.debounce(&jobs, id, query) else {
+                        break;
+                    };
+                    match job {
+                        Job::Query(id, query) => self.send_query(id, &query),
+                        Job::Activate(item, done) => {
+                            self.activate(item, done);
+                            return;
+                        }
+                    }
+                }
+                Some(Job::Activate(item, done)) => {
+                    self.activate(item, done);
+                    return;
+                }
+            }
+        }
+        self.kill();
+    }
+
+    /// Keep replacing the query until typing pauses; an activation wins right away
+    fn debounce(&self, jobs: &Receiver<Job>, mut id: u64, mut query: String) -> Option<Job> {
+        loop {
+            match jobs.recv_timeout(DEBOUNCE) {
+                Ok(Job::Query(next_id, next_query)) => {
+                    id = next_id;
+                    query = next_query;
+                }
+                Ok(activate) => return Some(activate),
+                Err(RecvTimeoutError::Timeout) => return Some(Job::Query(id, query)),
+                Err(RecvTimeoutError::Disconnected) => return None,
+            }
+        }
+    }
+
+    fn deadline(&self) -> Option<Instant> {
+        self.outstanding.front().map(|(_, sent)| *sent + self.timeout())
+    }
+
+    fn check_timeout(&mut self) {
+        let answered = self.state.lock().unwrap().answered;
+        self.outstanding.retain(|(id, _)| *id > answered);
+
+        if self.deadline().is_some_and(|deadline| deadline <= Instant::now()) {
+            eprintln!("[Plugins] {} did not answer within {:?}, restarting it", self.config.name, self.timeout());
+            self.kill();
+            self.set_status(PluginStatus::Failed(format!("{} timed out", self.config.name)));
+        }
+    }
+
+    fn send_query(&mut self, id: u64, query: &str) {
+        let line = json!({ "type": "query", "id": id, "query": query }).to_string();
+        if !self.send(&line) {
+            return;
+        }
+        self.outstanding.push_back((id, Instant::now()));
+
+        let mut state = self.state.lock().unwrap();
+        if state.status != PluginStatus::Waiting {
+            state.status = PluginStatus::Waiting;
+            state.generation += 1;
+        }
+    }
+
+    fn activate(&mut self, item: PluginItem, done: oneshot::Sender<bool>) {
+        let line = json!({ "type": "activate", "item": item }).to_string();
+        if !self.send(&line) {
+            let _ = done.send(false);
+            return;
+        }
+
+        // Closing stdin tells the plugin we're done; give it the timeout to act on the item
+        let Some(Running { mut child, stdin }) = self.running.take() else {
+            let _ = done.send(false);
+            return;
+        };
+        drop(stdin);
+        let deadline = Instant::now() + self.timeout();
+        while Instant::now() < deadline {
+            if let Ok(Some(_)) = child.try_wait() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        let _ = done.send(true);
+    }
+
+    /// Write one line, (re)starting the plugin if it isn't running
+    fn send(&mut self, line: &str) -> bool {
+        let alive = self
+            .running
+            .as_mut()
+            .is_some_and(|r| matches!(r.child.try_wait(), Ok(None)));
+        if !alive {
+            self.kill();
+            if !self.spawn() {
+                return false;
+            }
+        }
+
+        let Some(running) = self.running.as_mut() else {
+            return false;
+        };
+        let written = writeln!(running.stdin, "{}", line).and_then(|_| running.stdin.flush());
+        if let Err(e) = written {
+            eprintln!("[Plugins] Failed to write to {}: {}", self.config.name, e);
+            self.kill();
+            self.set_status(PluginStatus::Failed(format!("{} exited", self.config.name)));
+            return false;
+        }
+        true
+    }
+
+    fn spawn(&mut self) -> bool {
+        if self.restarts > MAX_RESTARTS {
+            return false;
+        }
+        if self.restarts == MAX_RESTARTS {
+            eprintln!("[Plugins] {} keeps failing, disabling it", self.config.name);
+            self.restarts += 1;
+            self.set_status(PluginStatus::Failed(format!("{} is disabled after repeated failures", self.config.name)));
+            return false;
+        }
+
+        let command = expand_home(terminal::split_command(&self.config.command));
+        let Some((program, args)) = command.split_first() else {
+            self.restarts = MAX_RESTARTS + 1;
+            self.set_status(PluginStatus::Failed(format!("{} has an empty command", self.config.name)));
+            return false;
+        };
+
+        let spawned = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn();
+        let mut child = match spawned {
+            Ok(child) => child,
+            Err(e) => {
+                eprintln!("[Plugins] Failed to start {} ({:?}): {}", self.config.name, program, e);
+                self.restarts += 1;
+                self.set_status(PluginStatus::Failed(format!("{} failed to start: {}", self.config.name, e)));
+                return false;
+            }
+        };
+
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            let _ = child.kill();
+            return false;
+        };
+        eprintln!("[Plugins] Started {}", self.config.name);
+
+        let process = {
+            let mut state = self.state.lock().unwrap();
+            state.process += 1;
+            state.process
+        };
+        let name = self.config.name.clone();
+        let state = Arc::clone(&self.state);
+        thread::spawn(move || read_responses(name, process, stdout, state));
+
+        self.running = Some(Running { child, stdin });
+        true
+    }
+
+    fn kill(&mut self) {
+        if let Some(mut running) = self.running.take() {
+            let _ = running.child.kill();
+            let _ = running.child.wait();
+            self.restarts += 1;
+        }
+        self.outstanding.clear();
+    }
+
+    fn set_status(&self, status: PluginStatus) {
+        let mut state = self.state.lock().unwrap();
+        state.status = status;
+        state.generation += 1;
+    }
+}
+
+/// Reader thread: apply each answer line until the plugin closes stdout.
+/// Once `process` has been replaced by a restart, whatever it still prints or its exit is ignored.
+fn read_responses(name: String, process: u64, stdout: impl std::io::Read, state: Arc<Mutex<PluginState>>) {
+    for line in BufReader::new(stdout).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<Response>(&line) {
+            Ok(response) => response,
+            Err(e) => {
+                eprintln!("[Plugins] {} sent an invalid line ({}): {}", name, e, line);
+                continue;
+            }
+        };
+
+        let mut state = state.lock().unwrap();
+        if state.process != process {
+            return;
+        }
+        match response {
+            Response::Results { id, items } if id > state.answered => {
+                state.answered = id;
+                state.items = items;
+                state.status = PluginStatus::Ready;
+            }
+            Response::Results { .. } => continue,
+            Response::Error { message } => {
+                state.status = PluginStatus::Failed(format!("{}: {}", name, message));
+            }
+        }
+        state.generation += 1;
+    }
+
+    // A plugin that dies mid-query would otherwise only show up as a timeout
+    let mut state = state.lock().unwrap();
+    if state.process == process && state.status == PluginStatus::Waiting {
+        state.status = PluginStatus::Failed(format!("{} exited", name));
+        state.generation += 1;
+    }
+}
+
+fn expand_home(command: Vec<String>) -> Vec<String> {
+    command
+        .into_iter()
+        .map(|arg| match arg.strip_prefix("~/") {
+            Some(rest) => dirs::home_dir().unwrap_or_default().join(rest).to_string_lossy().into_owned(),
+            None => arg,
+        })
+        .collect()
+}
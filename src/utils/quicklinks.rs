@@ -0,0 +1,114 @@
+//! Keyword-triggered web searches and links, e.g. `gh iced` -> GitHub search for "iced".
+
+use gio::AppLaunchContext;
+use serde::Deserialize;
+
+/// Placeholder in `url` replaced by the URL-encoded query
+const QUERY_PLACEHOLDER: &str = "{query}";
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Quicklink {
+    pub keyword: String,
+    pub name: String,
+    pub url: String,
+}
+
+impl Quicklink {
+    fn new(keyword: &str, name: &str, url: &str) -> Self {
+        Self {
+            keyword: keyword.to_string(),
+            name: name.to_string(),
+            url: url.to_string(),
+        }
+    }
+
+    /// Built-in quicklinks, used unless `default_quicklinks = false`
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self::new("g", "Google", "https://www.google.com/search?q={query}"),
+            Self::new("ddg", "DuckDuckGo", "https://duckduckgo.com/?q={query}"),
+            Self::new("w", "Wikipedia", "https://en.wikipedia.org/w/index.php?search={query}"),
+            Self::new("yt", "YouTube", "https://www.youtube.com/results?search_query={query}"),
+            Self::new("gh", "GitHub", "https://github.com/search?q={query}"),
+            Self::new("crate", "crates.io", "https://crates.io/search?q={query}"),
+        ]
+    }
+
+    pub fn takes_query(&self) -> bool {
+        self.url.contains(QUERY_PLACEHOLDER)
+    }
+
+    pub fn expand(&self, query: &str) -> String {
+        self.url.replace(QUERY_PLACEHOLDER, &url_encode(query))
+    }
+}
+
+/// Quicklinks from the config, with defaults for any keyword the config doesn't redefine
+pub fn merge(configured: Vec<Quicklink>, include_defaults: bool) -> Vec<Quicklink> {
+    let mut quicklinks = configured;
+    if include_defaults {
+        let defaults: Vec<Quicklink> = Quicklink::defaults()
+            .into_iter()
+            .filter(|d| !quicklinks.iter().any(|q| q.keyword.eq_ignore_ascii_case(&d.keyword)))
+            .collect();
+        quicklinks.extend(defaults);
+    }
+    quicklinks
+}
+
+/// Index of the quicklink whose keyword is the first word of `query`, and the rest of the query.
+/// The keyword only counts once whitespace follows it, so typing `g` still finds apps first.
+pub fn find(quicklinks: &[Quicklink], query: &str) -> Option<(usize, String)> {
+    let (keyword, rest) = query.trim_start().split_once(char::is_whitespace)?;
+    let rest = rest.trim();
+
+    quicklinks
+        .iter()
+        .position(|q| q.keyword.eq_ignore_ascii_case(keyword))
+        .map(|idx| (idx, rest.to_string()))
+}
+
+/// Open the expanded URL in the default browser
+pub fn open(quicklink: &Quicklink, query: &str) -> bool {
+    let url = quicklink.expand(query);
+    match gio::AppInfo::launch_default_for_uri(&url, AppLaunchContext::NONE) {
+        Ok(()) => true,
+        Err(e) => {
+            eprintln!("[Quicklinks] Failed to open {}: {}", url, e);
+            false
+        }
+    }
+}
+
+/// Percent-encode everything except RFC 3986 unreserved characters
+fn url_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keyword_needs_whitespace_after_it() {
+        let quicklinks = Quicklink::defaults();
+        assert_eq!(find(&quicklinks, "g"), None);
+        assert_eq!(find(&quicklinks, "yt"), None);
+        assert_eq!(find(&quicklinks, "gimp"), None);
+        assert_eq!(find(&quicklinks, "g "), Some((0, String::new())));
+        assert_eq!(find(&quicklinks, "  W rust lang "), Some((2, "rust lang".to_string())));
+    }
+
+    #[test]
+    fn expands_the_encoded_query() {
+        let google = &Quicklink::defaults()[0];
+        assert_eq!(google.expand("a b&c"), "https://www.google.com/search?q=a%20b%26c");
+    }
+}
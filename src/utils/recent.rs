@@ -0,0 +1,221 @@
+//! Recently used documents from the freedesktop `recently-used.xbel` bookmark file.
+
+use arc_swap::ArcSwapOption;
+use gio::prelude::*;
+use gio::{AppLaunchContext, DesktopAppInfo};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use crate::utils::normalize;
+use crate::utils::terminal;
+
+/// Only the newest documents are kept; older entries are rarely what people look for
+const MAX_DOCUMENTS: usize = 200;
+
+const BOOKMARK_NS: &str = "http://www.freedesktop.org/standards/desktop-bookmarks";
+
+/// Application that last opened a document, as recorded in the XBEL
+#[derive(Debug, Clone)]
+pub struct RecentApp {
+    /// Registered name, usually the program or desktop file name
+    pub name: String,
+    /// Command line with %u/%f placeholders
+    pub exec: String,
+    /// Human-readable name from the matching desktop entry, if there is one
+    pub display_name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct RecentDocument {
+    pub uri: String,
+    pub path: PathBuf,
+    pub name: String,
+    /// Folded file name (see `normalize::fold`)
+    pub name_lower: String,
+    pub app: Option<RecentApp>,
+    /// Unix timestamp of the last modification of the bookmark
+    pub modified: i64,
+}
+
+static RECENT_DOCUMENTS: ArcSwapOption<Vec<RecentDocument>> = ArcSwapOption::const_empty();
+static STARTED: AtomicBool = AtomicBool::new(false);
+static LOADING: AtomicBool = AtomicBool::new(false);
+
+fn xbel_path() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("recently-used.xbel")
+}
+
+/// Parse the bookmark file on a background thread (only the first call does anything)
+pub fn start() {
+    if !STARTED.swap(true, Ordering::AcqRel) {
+        reload();
+    }
+}
+
+/// Parse the bookmark file again once started; the old list stays until then
+pub fn refresh() {
+    if STARTED.load(Ordering::Acquire) {
+        reload();
+    }
+}
+
+fn reload() {
+    if LOADING.swap(true, Ordering::AcqRel) {
+        return;
+    }
+
+    thread::spawn(|| {
+        let documents = load();
+        eprintln!("[Recent] ✓ Loaded {} recent documents", documents.len());
+        RECENT_DOCUMENTS.store(Some(Arc::new(documents)));
+        LOADING.store(false, Ordering::Release);
+    });
+}
+
+/// Recent documents, newest first, or `None` while they are still loading
+pub fn documents() -> Option<Arc<Vec<RecentDocument>>> {
+    RECENT_DOCUMENTS.load_full()
+}
+
+/// ISO 8601 stamps are what GLib writes; older files used Unix timestamps
+fn parse_time(node: roxmltree::Node, iso_attr: &str) -> i64 {
+    node.attribute(iso_attr)
+        .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+        .map(|t| t.timestamp())
+        .or_else(|| node.attribute("timestamp").and_then(|s| s.parse().ok()))
+        .unwrap_or(0)
+}
+
+fn load() -> Vec<RecentDocument> {
+    let Ok(content) = fs::read_to_string(xbel_path()) else {
+        return Vec::new();
+    };
+    let document = match roxmltree::Document::parse(&content) {
+        Ok(document) => document,
+        Err(e) => {
+            eprintln!("[Recent] Failed to parse recently-used.xbel: {}", e);
+            return Vec::new();
+        }
+    };
+
+    let mut documents: Vec<RecentDocument> = document
+        .root_element()
+        .children()
+        .filter(|n| n.has_tag_name("bookmark"))
+        .filter_map(|bookmark| {
+            let uri = bookmark.attribute("href")?;
+            // Only local files that still exist can be opened
+            let path = gio::File::for_uri(uri).path().filter(|p| p.exists())?;
+            let name = path.file_name()?.to_string_lossy().to_string();
+
+            let app = bookmark
+                .descendants()
+                .filter(|n| n.has_tag_name((BOOKMARK_NS, "application")))
+                .max_by_key(|n| parse_time(*n, "modified"))
+                .and_then(|n| {
+                    let app_name = n.attribute("name")?.to_string();
+                    Some(RecentApp {
+                        display_name: app_name.clone(),
+                        name: app_name,
+                        exec: n.attribute("exec").unwrap_or_default().to_string(),
+                    })
+                });
+
+            Some(RecentDocument {
+                uri: uri.to_string(),
+                name_lower: normalize::fold(&name),
+                name,
+                path,
+                app,
+                modified: parse_time(bookmark, "modified"),
+            })
+        })
+        .collect();
+
+    documents.sort_by_key(|d| std::cmp::Reverse(d.modified));
+    documents.truncate(MAX_DOCUMENTS);
+
+    // Desktop file lookups only for the documents kept, once per app
+    let mut display_names: HashMap<String, String> = HashMap::new();
+    for app in documents.iter_mut().filter_map(|d| d.app.as_mut()) {
+        app.display_name = display_names
+            .entry(app.name.clone())
+            .or_insert_with(|| {
+                DesktopAppInfo::new(&format!("{}.desktop", app.name))
+                    .map(|info| info.name().to_string())
+                    .unwrap_or_else(|| app.name.clone())
+            })
+            .clone();
+    }
+    documents
+}
+
+/// Open `document` with the app that last opened it, falling back to the default handler
+pub fn open(document: &RecentDocument) -> bool {
+    if let Some(app) = &document.app {
+        if let Some(info) = DesktopAppInfo::new(&format!("{}.desktop", app.name)) {
+            if info.launch_uris(&[&document.uri], AppLaunchContext::NONE).is_ok() {
+                return true;
+            }
+        }
+        if run_exec(&app.exec, document) {
+            return true;
+        }
+    }
+
+    match gio::AppInfo::launch_default_for_uri(&document.uri, AppLaunchContext::NONE) {
+        Ok(()) => true,
+        Err(e) => {
+            eprintln!("[Recent] Failed to open {:?}: {}", document.path, e);
+            false
+        }
+    }
+}
+
+/// Run the recorded command line, e.g. `'gedit %u'`, with the document substituted in
+fn run_exec(exec: &str, document: &RecentDocument) -> bool {
+    // GLib stores the whole command line shell-quoted, so unquote once before splitting
+    let mut args = terminal::split_command(exec);
+    if let [single] = args.as_slice() {
+        if single.contains(char::is_whitespace) {
+            args = terminal::split_command(single);
+        }
+    }
+
+    let path = document.path.to_string_lossy();
+    let args: Vec<String> = args
+        .into_iter()
+        .map(|arg| match arg.as_str() {
+            "%u" | "%U" => document.uri.clone(),
+            "%f" | "%F" => path.to_string(),
+            _ => arg,
+        })
+        .collect();
+    let args = terminal::strip_field_codes(args);
+
+    let Some((program, rest)) = args.split_first() else {
+        return false;
+    };
+    match std::process::Command::new(program)
+        .args(rest)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+    {
+        Ok(child) => {
+            terminal::reap_in_background(child);
+            true
+        }
+        Err(e) => {
+            eprintln!("[Recent] Failed to run {:?}: {}", program, e);
+            false
+        }
+    }
+}
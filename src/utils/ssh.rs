@@ -0,0 +1,373 @@
+//! SSH hosts from `~/.ssh/config` (following `Include`) and `~/.ssh/known_hosts`.
+
+use arc_swap::ArcSwapOption;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use crate::utils::terminal;
+
+/// OpenSSH gives up on deeper `Include` nesting too
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SshHost {
+    /// Name passed to `ssh`: a config alias or a known_hosts entry
+    pub name: String,
+    pub name_lower: String,
+    /// Non-default port from a `[host]:port` known_hosts entry
+    pub port: Option<u16>,
+    /// `user@hostname` from the config, shown next to the alias
+    pub detail: String,
+}
+
+impl SshHost {
+    fn new(name: &str, port: Option<u16>) -> Self {
+        Self {
+            name: name.to_string(),
+            name_lower: name.to_lowercase(),
+            port,
+            detail: String::new(),
+        }
+    }
+
+    /// Frecency key, kept apart from desktop file ids
+    pub fn id(&self) -> String {
+        format!("ssh:{}", self.name)
+    }
+
+    pub fn command(&self) -> Vec<String> {
+        let mut command = vec!["ssh".to_string()];
+        if let Some(port) = self.port {
+            command.push("-p".to_string());
+            command.push(port.to_string());
+        }
+        command.push(self.name.clone());
+        command
+    }
+}
+
+static SSH_HOSTS: ArcSwapOption<Vec<SshHost>> = ArcSwapOption::const_empty();
+static STARTED: AtomicBool = AtomicBool::new(false);
+static LOADING: AtomicBool = AtomicBool::new(false);
+
+fn ssh_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".ssh")
+}
+
+/// Read the SSH files on a background thread (only the first call does anything)
+pub fn start() {
+    if !STARTED.swap(true, Ordering::AcqRel) {
+        reload();
+    }
+}
+
+/// Read the SSH files again once started; the old hosts stay until then
+pub fn refresh() {
+    if STARTED.load(Ordering::Acquire) {
+        reload();
+    }
+}
+
+fn reload() {
+    if LOADING.swap(true, Ordering::AcqRel) {
+        return;
+    }
+
+    thread::spawn(|| {
+        let hosts = load(&ssh_dir());
+        eprintln!("[SSH] ✓ Loaded {} hosts", hosts.len());
+        SSH_HOSTS.store(Some(Arc::new(hosts)));
+        LOADING.store(false, Ordering::Release);
+    });
+}
+
+/// Config aliases followed by known hosts, or `None` while they are still loading
+pub fn hosts() -> Option<Arc<Vec<SshHost>>> {
+    SSH_HOSTS.load_full()
+}
+
+fn load(ssh_dir: &Path) -> Vec<SshHost> {
+    let mut hosts = Vec::new();
+    parse_config(&ssh_dir.join("config"), ssh_dir, 0, &mut hosts);
+
+    let mut seen: HashSet<String> = hosts.iter().map(|h: &SshHost| h.name_lower.clone()).collect();
+    if let Ok(content) = fs::read_to_string(ssh_dir.join("known_hosts")) {
+        for host in parse_known_hosts(&content) {
+            if seen.insert(host.name_lower.clone()) {
+                hosts.push(host);
+            }
+        }
+    }
+    hosts
+}
+
+fn parse_config(path: &Path, ssh_dir: &Path, depth: usize, hosts: &mut Vec<SshHost>) {
+    if depth > MAX_INCLUDE_DEPTH {
+        eprintln!("[SSH] Include nested too deeply at {:?}", path);
+        return;
+    }
+    if let Ok(content) = fs::read_to_string(path) {
+        parse_config_content(&content, ssh_dir, depth, hosts);
+    }
+}
+
+/// Collect concrete `Host` aliases. Wildcard patterns only configure other hosts, and
+/// aliases excluded by a `!pattern` on the same line are dropped.
+fn parse_config_content(content: &str, ssh_dir: &Path, depth: usize, hosts: &mut Vec<SshHost>) {
+    // Hosts declared by the current `Host` block, as indices into `hosts`
+    let mut block: Vec<usize> = Vec::new();
+    let mut user: Option<String> = None;
+    let mut hostname: Option<String> = None;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        // `Keyword value` and `Keyword=value` are both valid
+        let (keyword, value) = match line.split_once(|c: char| c.is_whitespace() || c == '=') {
+            Some((keyword, value)) => (keyword, value.trim_start_matches(|c: char| c.is_whitespace() || c == '=')),
+            None => (line, ""),
+        };
+        let args = terminal::split_command(value);
+
+        match keyword.to_ascii_lowercase().as_str() {
+            "host" => {
+                finish_block(hosts, &block, user.take(), hostname.take());
+                block.clear();
+                let negated: Vec<&str> = args.iter().filter_map(|a| a.strip_prefix('!')).collect();
+                let aliases = args
+                    .iter()
+                    .filter(|a| !a.starts_with('!') && !a.contains(['*', '?']))
+                    .filter(|a| !negated.iter().any(|n| wildcard_match(n, a)));
+                for alias in aliases {
+                    if !hosts.iter().any(|h| h.name == *alias) {
+                        block.push(hosts.len());
+                        hosts.push(SshHost::new(alias, None));
+                    }
+                }
+            }
+            "match" => {
+                finish_block(hosts, &block, user.take(), hostname.take());
+                block.clear();
+            }
+            "user" => user = user.or(args.into_iter().next()),
+            "hostname" => hostname = hostname.or(args.into_iter().next()),
+            "include" => {
+                for pattern in args {
+                    for include in expand_include(&pattern, ssh_dir) {
+                        parse_config(&include, ssh_dir, depth + 1, hosts);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    finish_block(hosts, &block, user, hostname);
+}
+
+fn finish_block(hosts: &mut [SshHost], block: &[usize], user: Option<String>, hostname: Option<String>) {
+    if user.is_none() && hostname.is_none() {
+        return;
+    }
+    for &idx in block {
+        let target = hostname.as_deref().unwrap_or(&hosts[idx].name);
+        hosts[idx].detail = match &user {
+            Some(user) => format!("{}@{}", user, target),
+            None => target.to_string(),
+        };
+    }
+}
+
+/// Files matched by an `Include` argument; relative paths are taken from `~/.ssh`
+fn expand_include(pattern: &str, ssh_dir: &Path) -> Vec<PathBuf> {
+    let pattern = match pattern.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().unwrap_or_default().join(rest),
+        None => ssh_dir.join(pattern),
+    };
+
+    let mut paths = vec![PathBuf::from("/")];
+    for component in pattern.components().skip(1) {
+        let component = component.as_os_str().to_string_lossy();
+        if !component.contains(['*', '?']) {
+            paths = paths.into_iter().map(|p| p.join(component.as_ref())).collect();
+            continue;
+        }
+        paths = paths
+            .iter()
+            .filter_map(|dir| fs::read_dir(dir).ok())
+            .flat_map(|entries| entries.flatten())
+            .filter(|entry| {
+                // Like glob(3), wildcards don't match a leading dot
+                let name = entry.file_name().to_string_lossy().into_owned();
+                (!name.starts_with('.') || component.starts_with('.')) && wildcard_match(&component, &name)
+            })
+            .map(|entry| entry.path())
+            .collect();
+        // Glob results are processed in lexical order, as ssh does
+        paths.sort();
+    }
+    paths.retain(|p| p.is_file());
+    paths
+}
+
+/// `*` and `?` matching as used in ssh patterns
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and the text index it is currently matched up to
+    let mut star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    star = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Host names from known_hosts lines: `host1,host2 keytype key` or `[host]:port keytype key`.
+/// Hashed entries can't be recovered and CA/revoked markers aren't hosts.
+fn parse_known_hosts(content: &str) -> Vec<SshHost> {
+    let mut hosts = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('@') {
+            continue;
+        }
+        let Some(names) = line.split_whitespace().next() else {
+            continue;
+        };
+
+        for name in names.split(',') {
+            if name.starts_with('|') || name.starts_with('!') || name.contains(['*', '?']) {
+                continue;
+            }
+            let mut host = match name.strip_prefix('[').and_then(|n| n.split_once("]:")) {
+                Some((host, port)) => SshHost::new(host, port.parse().ok().filter(|p| *p != 22)),
+                None => SshHost::new(name, None),
+            };
+            if let Some(port) = host.port {
+                host.detail = format!("port {}", port);
+            }
+            hosts.push(host);
+        }
+    }
+    hosts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(content: &str) -> Vec<SshHost> {
+        let mut hosts = Vec::new();
+        parse_config_content(content, Path::new("/nonexistent"), 0, &mut hosts);
+        hosts
+    }
+
+    fn names(hosts: &[SshHost]) -> Vec<&str> {
+        hosts.iter().map(|h| h.name.as_str()).collect()
+    }
+
+    #[test]
+    fn negated_aliases_are_dropped() {
+        let hosts = config("Host alpha beta gamma !beta\nHost web1 web2 !web*\n");
+        assert_eq!(names(&hosts), ["alpha", "gamma"]);
+    }
+
+    #[test]
+    fn wildcard_only_host_lines_add_nothing() {
+        let hosts = config("Host box\n  User me\nHost *\n  User everyone\nHost * !box\n  HostName x\n");
+        assert_eq!(names(&hosts), ["box"]);
+        assert_eq!(hosts[0].detail, "me@box");
+    }
+
+    #[test]
+    fn user_and_hostname_become_the_detail() {
+        let hosts = config(
+            "# servers\nHost prod staging\n  User deploy\n  HostName=10.0.0.1\n  User ignored\n\
+             Host plain\n  Port 2200\nHost named\n  hostname named.example.com\nMatch all\n  User nobody\n",
+        );
+        assert_eq!(names(&hosts), ["prod", "staging", "plain", "named"]);
+        assert_eq!(hosts[0].detail, "deploy@10.0.0.1");
+        assert_eq!(hosts[1].detail, "deploy@10.0.0.1");
+        assert_eq!(hosts[2].detail, "");
+        assert_eq!(hosts[3].detail, "named.example.com");
+    }
+
+    #[test]
+    fn includes_are_read_relative_to_the_ssh_dir() {
+        let dir = std::env::temp_dir().join(format!("sierra-ssh-{}", std::process::id()));
+        fs::create_dir_all(dir.join("config.d")).unwrap();
+        fs::write(dir.join("config.d/b.conf"), "Host second\n").unwrap();
+        fs::write(dir.join("config.d/a.conf"), "Host first\n  User a\n").unwrap();
+        fs::write(dir.join("config.d/.hidden"), "Host hidden\n").unwrap();
+
+        let mut hosts = Vec::new();
+        parse_config_content("Include config.d/*\nHost last\n", &dir, 0, &mut hosts);
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(names(&hosts), ["first", "second", "last"]);
+        assert_eq!(hosts[0].detail, "a@first");
+    }
+
+    #[test]
+    fn known_hosts_ports_and_lists() {
+        let hosts = parse_known_hosts(
+            "alpha,10.0.0.2 ssh-ed25519 AAAA\n[beta]:2222 ssh-ed25519 AAAA\n[gamma]:22 ssh-rsa AAAA\n",
+        );
+        assert_eq!(names(&hosts), ["alpha", "10.0.0.2", "beta", "gamma"]);
+        assert_eq!(hosts[2].port, Some(2222));
+        assert_eq!(hosts[2].detail, "port 2222");
+        assert_eq!(hosts[2].command(), ["ssh", "-p", "2222", "beta"]);
+        assert_eq!(hosts[3].port, None);
+    }
+
+    #[test]
+    fn known_hosts_skip_hashes_markers_and_patterns() {
+        let hosts = parse_known_hosts(
+            "|1|F1E1KeoE/eEWhi10WpGv4OdiO6Y=|3988QV0VE8wmZL7suNrYQLITLCg= ssh-rsa AAAA\n\
+             @cert-authority *.example.com ssh-rsa AAAA\n\
+             @revoked old.example.com ssh-rsa AAAA\n\
+             # comment\n\
+             *.internal,!bad.internal,real ssh-rsa AAAA\n",
+        );
+        assert_eq!(names(&hosts), ["real"]);
+    }
+
+    #[test]
+    fn wildcard_edge_cases() {
+        assert!(wildcard_match("a*b*c", "abc"));
+        assert!(wildcard_match("a*b*c", "aXbYbZc"));
+        assert!(!wildcard_match("a*b*c", "aXbYcZ"));
+        assert!(wildcard_match("web*", "web"));
+        assert!(wildcard_match("web**", "web1"));
+        assert!(wildcard_match("*", ""));
+        assert!(wildcard_match("h?st", "host"));
+        assert!(!wildcard_match("h?st", "hst"));
+        assert!(!wildcard_match("", "x"));
+    }
+}
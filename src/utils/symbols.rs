@@ -0,0 +1,146 @@
+//! Embedded emoji and Unicode symbol table for the `:` picker, plus recently used symbols.
+
+use emojis::SkinTone;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+const RECENT_FILE: &str = ".cache/sierra/symbols.cache";
+const MAX_RECENT: usize = 30;
+
+/// Unicode blocks offered next to the emoji: arrows, math, currency, Greek, shapes...
+const SYMBOL_RANGES: [(u32, u32); 17] = [
+    (0x00A1, 0x00BF), // Latin-1 punctuation and symbols
+    (0x00D7, 0x00D7),
+    (0x00F7, 0x00F7),
+    (0x0391, 0x03C9), // Greek
+    (0x2010, 0x205E), // General punctuation
+    (0x2070, 0x209F), // Superscripts and subscripts
+    (0x20A0, 0x20C0), // Currency
+    (0x2100, 0x214F), // Letterlike symbols
+    (0x2150, 0x218B), // Number forms
+    (0x2190, 0x21FF), // Arrows
+    (0x2200, 0x22FF), // Mathematical operators
+    (0x2300, 0x23FF), // Miscellaneous technical
+    (0x2500, 0x257F), // Box drawing
+    (0x25A0, 0x25FF), // Geometric shapes
+    (0x2600, 0x26FF), // Miscellaneous symbols
+    (0x2700, 0x27BF), // Dingbats
+    (0x27F0, 0x297F), // Supplemental arrows
+];
+
+/// Single-tone skin variants; mixed-tone pairs would make the list unwieldy
+pub const SKIN_TONES: [SkinTone; 5] = [
+    SkinTone::Light,
+    SkinTone::MediumLight,
+    SkinTone::Medium,
+    SkinTone::MediumDark,
+    SkinTone::Dark,
+];
+
+#[derive(Debug, Clone)]
+pub struct Symbol {
+    pub glyph: String,
+    /// Lowercase Unicode/CLDR name, e.g. "thumbs up" or "rightwards arrow"
+    pub name: String,
+    /// Shortcodes such as "+1" or "thumbsup", space separated
+    pub keywords: String,
+    pub emoji: Option<&'static emojis::Emoji>,
+}
+
+impl Symbol {
+    /// Skin tone variants of this emoji, if it has any
+    pub fn skin_tone_variants(&self) -> Vec<String> {
+        let Some(emoji) = self.emoji.filter(|e| e.skin_tone().is_some()) else {
+            return Vec::new();
+        };
+        SKIN_TONES
+            .iter()
+            .filter_map(|tone| emoji.with_skin_tone(*tone))
+            .map(|e| e.as_str().to_string())
+            .collect()
+    }
+}
+
+static SYMBOLS: OnceLock<Vec<Symbol>> = OnceLock::new();
+
+/// Every emoji (default skin tone) followed by the symbol blocks, built on first use
+pub fn table() -> &'static [Symbol] {
+    SYMBOLS.get_or_init(|| {
+        let mut seen = HashSet::new();
+        let mut symbols: Vec<Symbol> = emojis::iter()
+            .map(|emoji| {
+                seen.insert(emoji.as_str().to_string());
+                Symbol {
+                    glyph: emoji.as_str().to_string(),
+                    name: emoji.name().to_lowercase(),
+                    keywords: emoji.shortcodes().collect::<Vec<_>>().join(" "),
+                    emoji: Some(emoji),
+                }
+            })
+            .collect();
+
+        for (start, end) in SYMBOL_RANGES {
+            for c in (start..=end).filter_map(char::from_u32) {
+                let glyph = c.to_string();
+                // Many miscellaneous symbols are emoji too; keep the emoji entry
+                if seen.contains(&glyph) {
+                    continue;
+                }
+                let Some(name) = unicode_names2::name(c) else {
+                    continue;
+                };
+                symbols.push(Symbol {
+                    glyph,
+                    name: name.to_string().to_lowercase(),
+                    keywords: String::new(),
+                    emoji: None,
+                });
+            }
+        }
+
+        eprintln!("[Symbols] Loaded {} symbols", symbols.len());
+        symbols
+    })
+}
+
+#[derive(Serialize, Deserialize)]
+struct RecentSymbols {
+    /// Glyphs, most recent first; skin tone variants are stored as picked
+    glyphs: Vec<String>,
+}
+
+fn get_recent_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(RECENT_FILE)
+}
+
+/// Recently picked glyphs, newest first
+pub fn load_recent() -> Vec<String> {
+    fs::read(get_recent_path())
+        .ok()
+        .and_then(|content| bincode::deserialize::<RecentSymbols>(&content).ok())
+        .map(|recent| recent.glyphs)
+        .unwrap_or_default()
+}
+
+/// Move `glyph` to the front of the recent list and persist it
+pub fn record_use(recent: &mut Vec<String>, glyph: &str) {
+    recent.retain(|g| g != glyph);
+    recent.insert(0, glyph.to_string());
+    recent.truncate(MAX_RECENT);
+
+    let path = get_recent_path();
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let encoded = bincode::serialize(&RecentSymbols { glyphs: recent.clone() });
+    if let Ok(encoded) = encoded {
+        if let Err(e) = fs::write(&path, encoded) {
+            eprintln!("Failed to save recent symbols: {}", e);
+        }
+    }
+}
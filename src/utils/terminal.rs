@@ -0,0 +1,125 @@
+//! Terminal emulator resolution for `Terminal=true` apps and other commands that need a tty.
+
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+
+/// Auto-detection order when no `terminal` is configured, with the arguments
+/// each emulator needs before the command to run
+const KNOWN_TERMINALS: [&str; 10] = [
+    "foot",
+    "kitty",
+    "alacritty -e",
+    "wezterm start --",
+    "ghostty -e",
+    "konsole -e",
+    "gnome-terminal --",
+    "xfce4-terminal -x",
+    "footclient",
+    "xterm -e",
+];
+
+/// Terminal prefix to use: the configured one, then `$TERMINAL`, then the first known emulator in PATH
+pub fn resolve(configured: Option<&str>) -> Option<Vec<String>> {
+    if let Some(configured) = configured.filter(|c| !c.trim().is_empty()) {
+        return Some(split_command(configured));
+    }
+
+    if let Ok(term) = std::env::var("TERMINAL") {
+        let parts = split_command(&term);
+        if parts.first().is_some_and(|bin| find_in_path(bin).is_some()) {
+            return Some(parts);
+        }
+    }
+
+    KNOWN_TERMINALS
+        .iter()
+        .map(|t| split_command(t))
+        .find(|parts| find_in_path(&parts[0]).is_some())
+}
+
+/// Spawn `command` inside the terminal, detached from the launcher
+pub fn spawn_in_terminal(terminal: &[String], command: &[String]) -> std::io::Result<()> {
+    let Some((program, args)) = terminal.split_first() else {
+        return Err(std::io::Error::new(std::io::ErrorKind::NotFound, "no terminal emulator"));
+    };
+
+    Command::new(program)
+        .args(args)
+        .args(command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(reap_in_background)
+}
+
+/// Wait for a detached child on its own thread, so a resident `--daemon` launcher
+/// doesn't collect zombies from everything it starts
+pub fn reap_in_background(mut child: Child) {
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+}
+
+/// Split a command line into arguments, honoring double/single quotes and backslash escapes
+/// (the quoting rules of the Desktop Entry `Exec` key are a subset of this)
+pub fn split_command(command: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+    let mut chars = command.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some('\''), '\'') => quote = None,
+            (Some('"'), '"') => quote = None,
+            (Some('"') | None, '\\') => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+                in_arg = true;
+            }
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            (_, c) => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+
+    if in_arg {
+        args.push(current);
+    }
+    args
+}
+
+/// Drop Desktop Entry field codes (`%f`, `%U`, `%i`, ...) from a parsed Exec line; `%%` becomes `%`
+pub fn strip_field_codes(args: Vec<String>) -> Vec<String> {
+    args.into_iter()
+        .filter(|arg| !(arg.len() == 2 && arg.starts_with('%') && arg != "%%"))
+        .map(|arg| arg.replace("%%", "%"))
+        .collect()
+}
+
+pub fn find_in_path(binary: &str) -> Option<PathBuf> {
+    if binary.contains('/') {
+        let path = PathBuf::from(binary);
+        return path.is_file().then_some(path);
+    }
+
+    std::env::var_os("PATH").and_then(|paths| {
+        std::env::split_paths(&paths)
+            .map(|dir| dir.join(binary))
+            .find(|p| p.is_file())
+    })
+}
@@ -22,6 +22,17 @@ pub struct WallpaperIndex {
     pub wallpapers: Vec<WallpaperEntry>,
 }
 
+impl WallpaperIndex {
+    /// Position of the wallpaper with file name `name`, or failing that the file stem
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.wallpapers.iter().position(|w| w.name == name).or_else(|| {
+            self.wallpapers
+                .iter()
+                .position(|w| w.path.file_stem().is_some_and(|stem| stem == name))
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WallpaperEntry {
     pub name: String,
@@ -210,16 +221,27 @@ impl WallpaperManager {
 
         let _ = Command::new("gslapper")
             .args(&gslapper_args)
-            .spawn();
+            .spawn()
+            .map(crate::utils::terminal::reap_in_background);
 
         // Update pywal colors from thumbnail (faster than full image)
         let _ = Command::new("wal")
             .args(&["-i", &thumbnail_path, "-n"])
-            .spawn();
+            .spawn()
+            .map(crate::utils::terminal::reap_in_background);
+
+        // Remembered for `wallpaper next` and the launcher's selection on its next start
+        let _ = fs::write(self.cache_dir.join("current"), &entry.name);
 
         eprintln!("[Wallpaper] Set to: {:?}", entry.name);
     }
 
+    /// Position of the wallpaper set last, by any launcher or `sierra-launcher wallpaper`
+    pub fn current_index(&self, wallpapers: &[WallpaperEntry]) -> Option<usize> {
+        let name = fs::read_to_string(self.cache_dir.join("current")).ok()?;
+        wallpapers.iter().position(|w| w.name == name.trim_end())
+    }
+
     /// ✅ NEW: Generate thumbnail from image using `image` crate (FAST)
     fn generate_image_thumbnail(source: &PathBuf, thumbnail: &PathBuf) {
         use image::ImageReader;
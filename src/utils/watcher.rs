@@ -1,6 +1,7 @@
 use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::{channel, Receiver};
+use std::time::Duration;
 
 pub struct ColorWatcher {
     _watcher: RecommendedWatcher,
@@ -41,4 +42,131 @@ impl ColorWatcher {
         }
         false
     }
-}
\ No newline at end of file
+}
+
+/// Watches the XDG `applications` directories for added, removed or edited `.desktop` files.
+pub struct AppDirWatcher {
+    watcher: RecommendedWatcher,
+    receiver: Receiver<notify::Result<Event>>,
+    /// Applications directories that don't exist yet, with the ancestor watched until they do
+    pending: Vec<(PathBuf, PathBuf)>,
+}
+
+impl AppDirWatcher {
+    pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
+        let (tx, rx) = channel();
+
+        let mut watcher = RecommendedWatcher::new(
+            move |res| {
+                let _ = tx.send(res);
+            },
+            Config::default(),
+        )?;
+
+        let mut watched = 0;
+        let mut pending = Vec::new();
+        for dir in Self::application_dirs() {
+            if dir.is_dir() {
+                if watcher.watch(&dir, RecursiveMode::Recursive).is_ok() {
+                    watched += 1;
+                }
+            } else if let Some(ancestor) = Self::watch_ancestor(&mut watcher, &dir) {
+                // e.g. ~/.local/share/applications before the first user-installed app
+                pending.push((dir, ancestor));
+            }
+        }
+
+        if watched == 0 && pending.is_empty() {
+            return Err("no applications directory to watch".into());
+        }
+
+        Ok(Self {
+            watcher,
+            receiver: rx,
+            pending,
+        })
+    }
+
+    /// Watch the nearest existing ancestor of `dir` for it being created
+    fn watch_ancestor(watcher: &mut RecommendedWatcher, dir: &Path) -> Option<PathBuf> {
+        let ancestor = dir.ancestors().skip(1).find(|a| a.is_dir())?;
+        watcher.watch(ancestor, RecursiveMode::NonRecursive).ok()?;
+        Some(ancestor.to_path_buf())
+    }
+
+    /// After something was created, watch pending directories that exist now, or their
+    /// ancestors one step closer. True when an applications directory appeared.
+    fn rearm(&mut self) -> bool {
+        let watched_before: Vec<PathBuf> = self.pending.iter().map(|(_, a)| a.clone()).collect();
+        let mut appeared = false;
+        for (dir, _) in std::mem::take(&mut self.pending) {
+            if dir.is_dir() && self.watcher.watch(&dir, RecursiveMode::Recursive).is_ok() {
+                appeared = true;
+            } else if let Some(ancestor) = Self::watch_ancestor(&mut self.watcher, &dir) {
+                self.pending.push((dir, ancestor));
+            }
+        }
+
+        for ancestor in watched_before {
+            if !self.pending.iter().any(|(_, still)| *still == ancestor) {
+                let _ = self.watcher.unwatch(&ancestor);
+            }
+        }
+        appeared
+    }
+
+    /// `$XDG_DATA_HOME/applications` followed by `applications` in each of `$XDG_DATA_DIRS`
+    pub fn application_dirs() -> Vec<PathBuf> {
+        let data_home = std::env::var_os("XDG_DATA_HOME")
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|h| h.join(".local/share")));
+
+        let data_dirs = std::env::var("XDG_DATA_DIRS")
+            .ok()
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+
+        data_home
+            .into_iter()
+            .chain(data_dirs.split(':').filter(|d| !d.is_empty()).map(PathBuf::from))
+            .map(|d| d.join("applications"))
+            .collect()
+    }
+
+    /// Block until a `.desktop` file changes or an applications directory is created,
+    /// then wait for the burst of events (package installs touch many files) to settle.
+    /// Returns false once the watcher is gone.
+    pub fn wait_for_change(&mut self) -> bool {
+        loop {
+            match self.receiver.recv() {
+                Ok(Ok(event)) if Self::is_desktop_change(&event) => break,
+                Ok(Ok(event)) if Self::is_creation(&event) && !self.pending.is_empty() => {
+                    // The new directory may already hold .desktop files copied in before it was watched
+                    if self.rearm() {
+                        break;
+                    }
+                }
+                Ok(_) => continue,
+                Err(_) => return false,
+            }
+        }
+
+        while self.receiver.recv_timeout(Duration::from_millis(500)).is_ok() {}
+        true
+    }
+
+    fn is_creation(event: &Event) -> bool {
+        matches!(event.kind, notify::EventKind::Create(_))
+    }
+
+    fn is_desktop_change(event: &Event) -> bool {
+        matches!(
+            event.kind,
+            notify::EventKind::Create(_) | notify::EventKind::Modify(_) | notify::EventKind::Remove(_)
+        ) && event
+            .paths
+            .iter()
+            .any(|p| p.extension().and_then(|e| e.to_str()) == Some("desktop"))
+    }
+}